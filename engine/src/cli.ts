@@ -6,30 +6,91 @@
  * stderr, non-zero exit on failure.
  */
 import { spawn } from 'node:child_process';
+import { createInterface } from 'node:readline/promises';
+import { StringDecoder } from 'node:string_decoder';
 import { fileURLToPath } from 'node:url';
 import { parseArgs } from 'node:util';
 
 import { listAgents } from './kild/agents.ts';
+import { archiveBranch, restoreArchive } from './kild/archive.ts';
+import { completionScript, isCompletionShell } from './kild/completions.ts';
+import {
+  CONFIG_KEYS,
+  type ConfigKey,
+  configEntries,
+  configFile,
+  effectiveConfigValue,
+  isConfigKey,
+  setConfigValue,
+} from './kild/config.ts';
 import {
   closeRoom,
   getLiveRooms,
+  getSessionEnv,
+  isLocalEngine,
   listSessions,
   openRoom,
+  pingEngine,
   postRoom,
   promptSession,
   spawnSession,
   stopSession,
 } from './kild/fleet/engine-client.ts';
-import { compactLiveRooms, formatCompactGitSummary } from './kild/fleet/rooms-status.ts';
-import { addProject, findProject, loadProjects, removeProject } from './kild/projects.ts';
 import {
+  compactLiveRooms,
+  formatCompactGitSummary,
+  formatPorcelainRoom,
+} from './kild/fleet/rooms-status.ts';
+import { createPr, ensurePushed, findPr, recordPr, repoForge } from './kild/forge.ts';
+import { branchDiff, branchDiffStat, reviewCommits } from './kild/git-review.ts';
+import {
+  approveOrphans,
+  type CrossProjectScan,
+  type Orphan,
+  type OrphanRemoveResult,
+  orphanDetails,
+  parseCleanupDecision,
+  removeOrphan,
+  scanForOrphans,
+  scanForOrphansAllProjects,
+} from './kild/orphans.ts';
+import {
+  addProject,
+  findProject,
+  loadProjects,
+  type Project,
+  projectHealth,
+  removeProject,
+} from './kild/projects.ts';
+import { generatePrBody, generatePrTitle } from './kild/pr-body.ts';
+import { readPromptSource } from './kild/prompt-source.ts';
+import { type PrPruneResult, pruneMergedPrWorktrees } from './kild/pr-prune.ts';
+import { activitySince, isIdleSince, parseDuration, parseSince } from './kild/since.ts';
+import {
+  type ReportPart,
+  worktreeListEntries,
+  worktreeStatusReport,
+} from './kild/status-report.ts';
+import {
+  abortRebase,
+  adoptWorktree,
+  ensureWorktree,
+  findWorktree,
   forceRemoveWorktree,
+  forkWorktree,
   listWorktrees,
   pruneMergedWorktrees,
+  rebaseWorktree,
   removeWorktree,
+  renameWorktree,
+  resolveBaseBranch,
+  restoreWorktree,
   type Worktree,
-  worktreePath,
+  type WorktreeRebaseResult,
+  worktreeName,
+  worktreeRef,
 } from './kild/worktree.ts';
+import { branchStashCount } from './kild/worktree-status.ts';
 
 const { values, positionals } = parseArgs({
   allowPositionals: true,
@@ -43,6 +104,25 @@ const { values, positionals } = parseArgs({
     participants: { type: 'string' }, // `kild room` participants, e.g. orchestrator,worker,reviewer
     detach: { type: 'boolean', default: false }, // `kild room open --detach`: print the id, don't stream
     base: { type: 'string' }, // base branch for the worktree + git-status baseline
+    'all-projects': { type: 'boolean', default: false }, // `worktree cleanup`: every project
+    interactive: { type: 'boolean', default: false }, // `worktree cleanup`: confirm each removal
+    draft: { type: 'boolean', default: false }, // `worktree pr`: open as a draft
+    title: { type: 'string' }, // `worktree pr`: PR title (default: generated)
+    archive: { type: 'boolean', default: false }, // `worktree rm`: bundle its commits first
+    checkout: { type: 'boolean', default: false }, // `worktree restore-archive`: recreate the tree
+    stat: { type: 'boolean', default: false }, // `worktree diff`: counts only, no patch
+    porcelain: { type: 'boolean', default: false }, // `kild rooms`: tab-separated, for scripts
+    idle: { type: 'string' }, // `kild sessions`: only those inactive this long (e.g. 30m)
+    // `worktree prune`: by merged PR, not git merge; `worktree ls --json`: add PR and CI
+    prs: { type: 'boolean', default: false },
+    'dry-run': { type: 'boolean', default: false }, // `worktree prune --prs`: list, don't remove
+    abort: { type: 'boolean', default: false }, // `worktree rebase`: abort one in progress
+    count: { type: 'string' }, // `kild ping`: how many health checks (default 4)
+    check: { type: 'boolean', default: false }, // `project ls`: report each path's health
+    prune: { type: 'boolean', default: false }, // `project ls`: unregister broken projects
+    // `worktree cleanup`: hand-made trees too; `worktree prune --prs`: don't ask first
+    yes: { type: 'boolean', default: false },
+    prompt: { type: 'string' }, // `kild run`: read the prompt from a file (`-`: stdin)
   },
 });
 
@@ -67,7 +147,7 @@ async function dispatch(): Promise<void> {
     case 'worktree':
       return worktree(action, rest);
     case 'run':
-      return run([action, ...rest].filter(Boolean).join(' '));
+      return run(await runPrompt([action, ...rest].filter(Boolean)));
     case 'room':
       return room(action, rest);
     case 'rooms':
@@ -75,9 +155,26 @@ async function dispatch(): Promise<void> {
     case 'fleet':
       return fleet(action, rest);
     case 'sessions':
-      return sessionsList();
+      return action === 'env' ? sessionEnvList(rest[0]) : sessionsList();
+    case 'since':
+      return since(action);
+    case 'events':
+      return events();
+    case 'config':
+      return config(action, rest);
+    case 'whereis':
+      return whereis(action);
+    case 'completions':
+      return completions(action);
+    case '__complete':
+      return complete(action);
+    case 'ping':
+      return ping();
     default:
-      console.error('usage: kild <project|agent|worktree|run|room|rooms|fleet|sessions> …');
+      console.error(
+        'usage: kild <project|agent|worktree|run|room|rooms|fleet|sessions|since|events|' +
+          'config|whereis|completions|ping> …',
+      );
       process.exit(2);
   }
 }
@@ -85,12 +182,14 @@ async function dispatch(): Promise<void> {
 async function project(action: string | undefined, args: string[]): Promise<void> {
   if (action === 'ls') {
     const projects = await loadProjects();
+    if (values.check || values.prune) return projectCheck(projects);
     if (json) return void console.log(JSON.stringify(projects, null, 2));
     if (projects.length === 0) return void console.error('no projects registered');
     for (const p of projects) console.log(`${p.name}\t${p.path}`);
   } else if (action === 'add') {
-    const [name, path] = args;
-    if (!name || !path) throw new Error('usage: kild project add <name> <path>');
+    // One arg is the path; the name then comes from its origin remote (or dir name).
+    const [path, name] = args.length === 1 ? [args[0]] : [args[1], args[0]];
+    if (!path) throw new Error('usage: kild project add [name] <path>');
     const p = await addProject(name, path);
     console.log(json ? JSON.stringify(p, null, 2) : `added ${p.name} → ${p.path}`);
   } else if (action === 'rm') {
@@ -103,6 +202,33 @@ async function project(action: string | undefined, args: string[]): Promise<void
   }
 }
 
+/** `kild project ls --check [--prune]` — each project's health; `--prune` unregisters
+ *  the broken ones (after a prompt, or `--force`). The repos themselves are untouched. */
+async function projectCheck(projects: Project[]): Promise<void> {
+  const checked = await Promise.all(
+    projects.map(async (p) => ({ ...p, health: await projectHealth(p) })),
+  );
+  const broken = checked.filter((p) => p.health !== 'ok');
+  if (!values.prune) {
+    if (json) return void console.log(JSON.stringify(checked, null, 2));
+    for (const p of checked) console.log(`${p.name}\t${p.health}\t${p.path}`);
+    return;
+  }
+  if (broken.length === 0) return void console.error('every project is healthy');
+  for (const p of broken) console.error(`${p.name}\t${p.health}\t${p.path}`);
+  if (!values.force) {
+    if (!process.stdin.isTTY) throw new Error('not a terminal — pass --force to prune');
+    const rl = createInterface({ input: process.stdin, output: process.stderr });
+    const answer = await rl.question(`unregister ${broken.length} project(s)? [y/N] `);
+    rl.close();
+    if (!/^y(es)?$/i.test(answer.trim())) return void console.error('nothing removed');
+  }
+  for (const p of broken) await removeProject(p.name);
+  const names = broken.map((p) => p.name);
+  if (json) console.log(JSON.stringify({ removed: names }, null, 2));
+  else console.log(`removed: ${names.join(', ')}`);
+}
+
 async function agent(action: string | undefined, args: string[]): Promise<void> {
   const projectPath = values.project
     ? ((await findProject(values.project))?.path ?? values.project)
@@ -140,6 +266,7 @@ async function worktree(action: string | undefined, args: string[]): Promise<voi
   const repo = values.project
     ? ((await findProject(values.project))?.path ?? values.project)
     : undefined;
+  if (action === 'cleanup' && values['all-projects']) return worktreeCleanup();
   if (!repo) throw new Error('--project <name|path> is required');
   const q = `project=${encodeURIComponent(repo)}`;
 
@@ -154,12 +281,18 @@ async function worktree(action: string | undefined, args: string[]): Promise<voi
     const trees = engineUp
       ? await engineFetch<Worktree[]>(`/api/worktrees?${q}`)
       : (await listWorktrees(repo)).filter((t) => t.branch.startsWith('kild/'));
-    if (json) return void console.log(JSON.stringify(trees, null, 2));
+    if (json) {
+      // Each tree's status too (see WorktreeListEntry), so a tool needs one call. The
+      // PR and CI cost a forge call per tree, so only with `--prs`.
+      const base = await resolveBaseBranch(repo, values.base);
+      const entries = await worktreeListEntries(repo, trees, base, values.prs);
+      return void console.log(JSON.stringify(entries, null, 2));
+    }
     if (trees.length === 0) return void console.error('no kild worktrees');
     for (const t of trees) console.log(`${t.branch}\t${t.path}`);
   } else if (action === 'rm') {
     const [name] = args;
-    if (!name) throw new Error('usage: kild worktree rm <name> --project <p>');
+    if (!name) throw new Error('usage: kild worktree rm <name> --project <p> [--archive]');
     if (engineUp) {
       await engineFetch(`/api/worktrees`, {
         method: 'DELETE',
@@ -167,13 +300,76 @@ async function worktree(action: string | undefined, args: string[]): Promise<voi
         body: JSON.stringify({ project: repo, name, force: values.force }),
       });
     } else {
-      const result = values.force
-        ? await forceRemoveWorktree(repo, worktreePath(name))
-        : await removeWorktree(repo, worktreePath(name));
+      // Removed where git has it, which isn't worktreePath(name) for a tree made under
+      // an earlier worktreeRoot.
+      const tree = await findWorktree(repo, name);
+      const result = !tree
+        ? ({ ok: false, code: 'not_found' } as const)
+        : values.force
+          ? await forceRemoveWorktree(repo, tree.path)
+          : await removeWorktree(repo, tree.path);
       if (!result.ok) throw new Error(removeRefusalMessage(name, result));
     }
+    // Stashes live in the repo, not the tree, so they survive — but a stash made in a
+    // tree that's gone is easy to forget. Checked only now the tree really is gone; a
+    // failed probe stays silent (nothing known).
+    const stashes = await branchStashCount(repo, worktreeRef(name));
+    if (stashes) console.error(`warning: ${stashes} stash(es) were made on ${worktreeRef(name)}`);
+    // Only once the removal went through — a refused one must not leave a bundle behind.
+    // `rm` keeps the branch, so its commits are still there to archive.
+    if (values.archive) {
+      const base = await resolveBaseBranch(repo, values.base);
+      const bundle = await archiveBranch(repo, worktreeRef(name), base);
+      console.error(bundle ? `archived ${name} → ${bundle}` : `no commits to archive vs ${base}`);
+    }
     if (json) console.log(JSON.stringify({ ok: true, name }, null, 2));
     else console.log(`${values.force ? 'force-removed' : 'removed'} worktree ${name}`);
+  } else if (action === 'mv') {
+    const [from, to] = args;
+    if (!from || !to) throw new Error('usage: kild worktree mv <name> <new-name> --project <p>');
+    let renamed: Worktree;
+    if (engineUp) {
+      renamed = await engineFetch<Worktree>(`/api/worktrees/rename`, {
+        method: 'POST',
+        headers: { 'content-type': 'application/json' },
+        body: JSON.stringify({ project: repo, from, to }),
+      });
+    } else {
+      const result = await renameWorktree(repo, from, to);
+      if (!result.ok) {
+        throw new Error(
+          result.code === 'exists'
+            ? `worktree '${to}' already exists`
+            : removeRefusalMessage(from, { code: result.code }),
+        );
+      }
+      renamed = result.worktree;
+    }
+    if (json) console.log(JSON.stringify(renamed, null, 2));
+    else console.log(`renamed worktree ${from} → ${to} (${renamed.path})`);
+  } else if (action === 'prune' && values.prs) {
+    const prunePrs = (dryRun: boolean, names?: string[]) =>
+      engineUp
+        ? engineFetch<PrPruneResult>(`/api/worktrees/prune`, {
+            method: 'POST',
+            headers: { 'content-type': 'application/json' },
+            body: JSON.stringify({ project: repo, prs: true, dryRun, names }),
+          })
+        : pruneMergedPrWorktrees(repo, new Set(), dryRun, names);
+    const dryRun = values['dry-run'];
+    // Removal deletes branches too, so it is confirmed first: the candidates come from a
+    // dry run, and only those are pruned once approved.
+    let result = await prunePrs(true);
+    if (!dryRun && result.pruned.length > 0) {
+      const approved = values.yes || (await confirmPrPrune(result.pruned));
+      result = approved ? await prunePrs(false, result.pruned) : { ...result, pruned: [] };
+      if (!approved) console.error('nothing removed');
+    }
+    if (json) return void console.log(JSON.stringify(result, null, 2));
+    for (const { name, reason } of result.skipped) console.error(`kept ${name}: ${reason}`);
+    for (const { name, error } of result.failed) console.error(`failed ${name}: ${error}`);
+    const verb = dryRun ? 'would prune' : 'pruned';
+    console.log(result.pruned.length ? `${verb}: ${result.pruned.join(', ')}` : 'nothing to prune');
   } else if (action === 'prune') {
     const pruned = engineUp
       ? (
@@ -186,8 +382,282 @@ async function worktree(action: string | undefined, args: string[]): Promise<voi
       : await pruneMergedWorktrees(repo);
     if (json) console.log(JSON.stringify({ pruned }, null, 2));
     else console.log(pruned.length ? `pruned: ${pruned.join(', ')}` : 'nothing to prune');
+  } else if (action === 'cleanup') {
+    return worktreeCleanup(repo);
+  } else if (action === 'restore') {
+    // Its tree is gone, so no live session can be using it — runs directly.
+    const [name] = args;
+    if (!name) throw new Error('usage: kild worktree restore <name> --project <p>');
+    const restored = await restoreWorktree(repo, name);
+    if (json) console.log(JSON.stringify(restored, null, 2));
+    else console.log(`restored worktree ${name} → ${restored.path}`);
+  } else if (action === 'rebase') {
+    const [name] = args;
+    if (!name) {
+      throw new Error('usage: kild worktree rebase <name> --project <p> [--base <b>|--abort]');
+    }
+    // Through the engine when it's up, so a tree a live session is working in is refused.
+    const rebase = (body: { base?: string; abort?: boolean }) =>
+      engineFetch<WorktreeRebaseResult>(`/api/worktrees/rebase`, {
+        method: 'POST',
+        headers: { 'content-type': 'application/json' },
+        body: JSON.stringify({ project: repo, name, ...body }),
+      });
+    if (values.abort) {
+      if (engineUp) await rebase({ abort: true });
+      else await abortRebase(repo, name);
+      if (json) console.log(JSON.stringify({ ok: true, name }, null, 2));
+      else console.log(`aborted rebase of ${name}`);
+      return;
+    }
+    const base = await resolveBaseBranch(repo, values.base);
+    const result = engineUp ? await rebase({ base }) : await rebaseWorktree(repo, name, base);
+    if (json) return void console.log(JSON.stringify({ ...result, name, base }, null, 2));
+    if (!result.ok) throw new Error(rebaseRefusalMessage(name, result));
+    console.log(
+      result.status === 'up_to_date'
+        ? `${name} is already up to date with ${base}`
+        : `rebased ${name} onto ${base} cleanly (${result.commits} commit(s))`,
+    );
+  } else if (action === 'add') {
+    // Just the tree and its branch — no session. `run`/`room` with `--worktree <name>`
+    // attach to it later (ensureWorktree never resets an existing tree).
+    const [name] = args;
+    if (!name) throw new Error('usage: kild worktree add <name> --project <p> [--base <b>]');
+    const base = await resolveBaseBranch(repo, values.base);
+    const created = await ensureWorktree(repo, name, base);
+    if (json) console.log(JSON.stringify(created, null, 2));
+    else console.log(`worktree ${name} → ${created.path} (from ${base})`);
+  } else if (action === 'fork') {
+    // A brand-new tree no session can be using yet — runs directly.
+    const [source, name] = args;
+    if (!source || !name) {
+      throw new Error('usage: kild worktree fork <name> <new-name> --project <p>');
+    }
+    const forked = await forkWorktree(repo, source, name);
+    if (json) console.log(JSON.stringify(forked, null, 2));
+    else console.log(`forked worktree ${source} → ${name} (${forked.path})`);
+  } else if (action === 'restore-archive') {
+    const [bundle] = args;
+    if (!bundle) {
+      throw new Error('usage: kild worktree restore-archive <bundle> --project <p> [--checkout]');
+    }
+    const restored = await restoreArchive(repo, bundle);
+    const wt = values.checkout
+      ? await restoreWorktree(repo, worktreeName(restored.branch))
+      : undefined;
+    if (json) return void console.log(JSON.stringify({ ...restored, path: wt?.path }, null, 2));
+    console.log(`restored ${restored.branch} (${restored.commits} commit(s))`);
+    if (wt) console.log(`worktree → ${wt.path}`);
+  } else if (action === 'log') {
+    // Read from the main checkout by ref, so a removed tree's kept branch works too.
+    const [name] = args;
+    if (!name) throw new Error('usage: kild worktree log <name> --project <p> [--base <b>]');
+    const base = await resolveBaseBranch(repo, values.base);
+    const { commits, error } = await reviewCommits(repo, base, worktreeRef(name));
+    if (error) throw new Error(error);
+    if (json) return void console.log(JSON.stringify({ base, commits }, null, 2));
+    if (commits.length === 0) return void console.error(`no commits on ${name} vs ${base}`);
+    for (const c of commits) {
+      const stat = `+${c.additions} -${c.deletions}`;
+      console.log(`${c.sha.slice(0, 7)}  ${c.subject}  (${c.author}, ${stat})`);
+    }
+  } else if (action === 'status') {
+    const [name] = args;
+    if (!name) throw new Error('usage: kild worktree status <name> --project <p> [--base <b>]');
+    const base = await resolveBaseBranch(repo, values.base);
+    const report = await worktreeStatusReport(repo, name, base);
+    if (json) return void console.log(JSON.stringify(report, null, 2));
+    const part = <T>(p: ReportPart<T>, show: (value: T) => string) =>
+      p.ok ? show(p.value) : `unavailable (${p.unavailable})`;
+    console.log(`${report.branch}\t${report.path}`);
+    const git = part(report.git, (g) => {
+      const flags = `${g.dirty ? ', dirty' : ''}${g.conflictsWithBase ? ', CONFLICTS' : ''}`;
+      return `+${g.ahead}/-${g.behind} vs ${base}${flags}`;
+    });
+    console.log(`  git:    ${git}`);
+    console.log(`  pushed: ${part(report.pushed, (p) => (p ? 'yes' : 'no'))}`);
+    console.log(`  pr:     ${part(report.pr, (pr) => (pr ? `${pr.state} ${pr.url}` : 'none'))}`);
+    console.log(`  ci:     ${part(report.ci, (ci) => ci)}`);
+  } else if (action === 'diff') {
+    const [name] = args;
+    if (!name) {
+      throw new Error('usage: kild worktree diff <name> --project <p> [--stat] [--base <b>]');
+    }
+    const base = await resolveBaseBranch(repo, values.base);
+    if (values.stat) {
+      const { stat, error } = await branchDiffStat(repo, base, worktreeRef(name));
+      if (error) throw new Error(error);
+      if (json) return void console.log(JSON.stringify({ base, ...stat }, null, 2));
+      const counts = `+${stat.additions} -${stat.deletions}`;
+      const binary = stat.binaryFiles ? ` (${stat.binaryFiles} binary)` : '';
+      return void console.log(`${stat.files} file(s) changed${binary}, ${counts} vs ${base}`);
+    }
+    const { patch, error } = await branchDiff(repo, base, worktreeRef(name));
+    if (error) throw new Error(error);
+    if (json) return void console.log(JSON.stringify({ base, patch }, null, 2));
+    await writeStdout(patch);
+  } else if (action === 'pr-body') {
+    const [name] = args;
+    if (!name) throw new Error('usage: kild worktree pr-body <name> --project <p> [--base <b>]');
+    const base = await resolveBaseBranch(repo, values.base);
+    const { body } = await prDraft(repo, name, base);
+    if (json) console.log(JSON.stringify({ base, body }, null, 2));
+    else process.stdout.write(body);
+  } else if (action === 'pr') {
+    const [name] = args;
+    if (!name) throw new Error('usage: kild worktree pr <name> --project <p> [--title <t>]');
+    const forge = await repoForge(repo);
+    if (!forge) throw new Error(`origin remote of ${repo} is not on a known forge`);
+    const existing = await findPr(repo, forge, worktreeRef(name));
+    if (existing?.state === 'open') {
+      throw new Error(`a PR for ${name} is already open: ${existing.url}`);
+    }
+    const base = await resolveBaseBranch(repo, values.base);
+    const draft = await prDraft(repo, name, base);
+    await ensurePushed(repo, worktreeRef(name));
+    const result = await createPr(repo, forge, {
+      branch: worktreeRef(name),
+      base,
+      title: values.title ?? draft.title,
+      body: draft.body,
+      draft: values.draft,
+    });
+    if (!result.ok && result.code === 'exists') {
+      throw new Error(`a PR for ${name} already exists: ${result.error}`);
+    }
+    if (!result.ok) throw new Error(result.error);
+    await recordPr(repo, worktreeRef(name), { state: 'open', url: result.url });
+    if (json) console.log(JSON.stringify({ url: result.url }, null, 2));
+    else console.log(result.url);
+  } else if (action === 'adopt') {
+    // A hand-made tree has no kild session on it yet, so this runs directly.
+    const [wtPath] = args;
+    if (!wtPath) throw new Error('usage: kild worktree adopt <path> --project <p>');
+    const adopted = await adoptWorktree(repo, wtPath);
+    if (json) console.log(JSON.stringify(adopted, null, 2));
+    else console.log(`adopted ${wtPath} as ${adopted.branch} → ${adopted.path}`);
   } else {
-    throw new Error('usage: kild worktree <ls|rm|prune> --project <p> [--force]');
+    throw new Error(
+      'usage: kild worktree <ls|add|status|log|diff|pr-body|pr|rm|mv|fork|rebase|restore|' +
+        'restore-archive|prune|cleanup|adopt> --project <p>',
+    );
+  }
+}
+
+/** Write to stdout and wait for it to flush (a large patch to a pipe would otherwise be
+ *  cut off by the `process.exit` after dispatch). A reader that quits early — `| head`,
+ *  a pager closed before the end — is not an error. */
+function writeStdout(text: string): Promise<void> {
+  return new Promise((resolve, reject) => {
+    process.stdout.once('error', (err: NodeJS.ErrnoException) => {
+      if (err.code === 'EPIPE') resolve();
+      else reject(err);
+    });
+    process.stdout.write(text, () => resolve());
+  });
+}
+
+/** The generated PR title + body for worktree `name` vs `base`, read by ref from the
+ *  main checkout. */
+async function prDraft(
+  repo: string,
+  name: string,
+  base: string,
+): Promise<{ title: string; body: string }> {
+  const [log, diff] = await Promise.all([
+    reviewCommits(repo, base, worktreeRef(name)),
+    branchDiffStat(repo, base, worktreeRef(name)),
+  ]);
+  const error = log.error ?? diff.error;
+  if (error) throw new Error(error);
+  return {
+    title: generatePrTitle(name, log.commits),
+    body: generatePrBody(log.commits, diff.stat),
+  };
+}
+
+/** `kild worktree cleanup` — remove the kild leftovers `worktree ls` can't show: branches
+ *  whose tree was removed, stale entries for deleted trees, and clean hand-made trees
+ *  under the worktree root (`worktree adopt` keeps one instead). Without `repo` (i.e.
+ *  `--all-projects`) every registered project is scanned. Unmerged branches are kept
+ *  unless `--force`; `--interactive` asks before each removal. A hand-made tree is the
+ *  user's own, so a batch run only reports it — removing it takes `--yes` or an
+ *  `--interactive` yes. Orphans have no kild session, so no live session can be using
+ *  them — this runs directly rather than through the engine. */
+async function worktreeCleanup(repo?: string): Promise<void> {
+  const scan: CrossProjectScan = repo
+    ? { orphans: await scanForOrphans(repo), skipped: [] }
+    : await scanForOrphansAllProjects();
+  for (const skip of scan.skipped) {
+    console.error(`warning: skipped project ${skip.project} (${skip.path}): ${skip.reason}`);
+  }
+  const results: Array<Orphan & OrphanRemoveResult> = [];
+  let candidates = scan.orphans;
+  if (!values.interactive && !values.yes) {
+    const error = 'made by hand; re-run with --yes or --interactive to remove it';
+    for (const orphan of candidates) {
+      if (orphan.kind === 'untracked_worktree') results.push({ ...orphan, ok: false, error });
+    }
+    candidates = candidates.filter((orphan) => orphan.kind !== 'untracked_worktree');
+  }
+  const orphans = values.interactive ? await confirmOrphans(candidates) : candidates;
+  for (const orphan of orphans) {
+    // `-D` discards unmerged commits; flag a branch no remote has a full copy of.
+    if (values.force && orphan.kind === 'branch' && !(await orphanDetails(orphan)).pushed) {
+      console.error(`warning: ${orphanLabel(orphan)} is not fully pushed to a remote`);
+    }
+    results.push({ ...orphan, ...(await removeOrphan(orphan, values.force ?? false)) });
+  }
+  if (json) return void console.log(JSON.stringify({ results, skipped: scan.skipped }, null, 2));
+  if (results.length === 0) return void console.error('no orphaned kild resources removed');
+  for (const r of results) {
+    console.log(r.ok ? `removed ${orphanLabel(r)}` : `kept ${orphanLabel(r)}: ${r.error}`);
+  }
+}
+
+function orphanLabel(orphan: Orphan): string {
+  const where = orphan.project ? `${orphan.project}: ` : '';
+  if (orphan.kind === 'branch') return `${where}branch ${orphan.branch}`;
+  if (orphan.kind === 'stale_worktree') return `${where}stale worktree entry ${orphan.path}`;
+  return `${where}untracked worktree ${orphan.path} (${orphan.branch})`;
+}
+
+/** Ask once before `worktree prune --prs` removes `names` (trees and their branches).
+ *  Refuses without a terminal: a script must say `--yes`. */
+async function confirmPrPrune(names: string[]): Promise<boolean> {
+  if (!process.stdin.isTTY) throw new Error('not a terminal — pass --yes to prune');
+  for (const name of names) console.error(`${name}\t${worktreeRef(name)}`);
+  const rl = createInterface({ input: process.stdin, output: process.stderr });
+  const answer = await rl.question(`remove ${names.length} worktree(s) and their branches? [y/N] `);
+  rl.close();
+  return /^y(es)?$/i.test(answer.trim());
+}
+
+/** `cleanup --interactive`: ask y/n/all/quit per orphan on the terminal. Refuses
+ *  without a TTY — a script should run the batch cleanup instead. */
+async function confirmOrphans(orphans: Orphan[]): Promise<Orphan[]> {
+  if (!process.stdin.isTTY) {
+    throw new Error('--interactive needs a terminal; use --yes to remove every orphan');
+  }
+  const rl = createInterface({ input: process.stdin, output: process.stderr });
+  try {
+    return await approveOrphans(orphans, async (orphan) => {
+      const info = await orphanDetails(orphan);
+      const details = [
+        info.sizeKb !== undefined ? `${info.sizeKb} KiB` : undefined,
+        info.dirty === undefined ? undefined : info.dirty ? 'dirty' : 'clean',
+        info.pushed === false ? 'not pushed' : undefined,
+      ].filter(Boolean);
+      const suffix = details.length ? ` [${details.join(', ')}]` : '';
+      for (;;) {
+        const answer = await rl.question(`remove ${orphanLabel(orphan)}${suffix}? [y/n/a/q] `);
+        const decision = parseCleanupDecision(answer);
+        if (decision) return decision;
+      }
+    });
+  } finally {
+    rl.close();
   }
 }
 
@@ -203,14 +673,34 @@ function removeRefusalMessage(
   return `worktree '${name}' was not found`;
 }
 
+function rebaseRefusalMessage(
+  name: string,
+  result: Extract<WorktreeRebaseResult, { ok: false }>,
+): string {
+  if (result.code === 'not_found') return `worktree '${name}' was not found`;
+  if (result.code === 'in_use') return `worktree '${name}' is in use by a live session`;
+  if (result.code === 'dirty') return `${name} has uncommitted changes: ${result.files.join(', ')}`;
+  return (
+    `conflicts in: ${result.files.join(', ')} — resolve them in ${result.path} and ` +
+    `run \`git rebase --continue\`, or \`kild worktree rebase ${name} --abort\``
+  );
+}
+
 async function engineRunning(): Promise<boolean> {
   return fetch(`${ENGINE}/api/health`)
     .then((r) => r.ok)
     .catch(() => false);
 }
 
+/** The `kild run` prompt: the words on the command line, or `--prompt <file|->`. */
+async function runPrompt(words: string[]): Promise<string> {
+  if (values.prompt === undefined) return words.join(' ');
+  if (words.length > 0) throw new Error('give the prompt as arguments or --prompt, not both');
+  return readPromptSource(values.prompt);
+}
+
 async function run(prompt: string): Promise<void> {
-  if (!prompt) throw new Error('usage: kild run <prompt…>');
+  if (!prompt) throw new Error('usage: kild run <prompt…> | --prompt <file|->');
   // If the engine is up, run THROUGH it so the session shows up in the cockpit;
   // otherwise run the agent in-process so the CLI works standalone.
   return (await engineRunning()) ? runViaEngine(prompt) : runViaWorker(prompt);
@@ -238,12 +728,174 @@ async function fleet(action: string | undefined, args: string[]): Promise<void>
 
 /** `kild sessions` / `kild fleet ls` — list live sessions (fleet drivers + runs). */
 async function sessionsList(): Promise<void> {
-  const sessions = await listSessions();
+  const now = Date.now();
+  let sessions = await listSessions();
+  if (values.idle !== undefined) {
+    const threshold = parseDuration(values.idle);
+    if (threshold === undefined) throw new Error('usage: kild sessions --idle <30m|2h|1d|1w>');
+    sessions = sessions.filter((s) => isIdleSince(s.lastActivity, now - threshold));
+  }
   if (json) return void console.log(JSON.stringify(sessions, null, 2));
   if (sessions.length === 0) return void console.error('no live sessions');
   for (const s of sessions) {
-    console.log(`${s.id}\t${s.agent ?? 'default'}${s.model ? ` (${s.model})` : ''}`);
+    const model = s.model ? ` (${s.model})` : '';
+    const idle = s.lastActivity === undefined ? 'no activity' : formatAge(now - s.lastActivity);
+    console.log(`${s.id}\t${s.agent ?? 'default'}${model}\tidle ${idle}`);
+  }
+}
+
+/** An age in the coarsest whole unit (`42m`, `3h`, `2d`) — for list columns. */
+function formatAge(ms: number): string {
+  const minutes = Math.floor(ms / 60_000);
+  if (minutes < 60) return `${minutes}m`;
+  const hours = Math.floor(minutes / 60);
+  return hours < 24 ? `${hours}h` : `${Math.floor(hours / 24)}d`;
+}
+
+/** `kild completions <bash|zsh|fish>` — the shell completion script, on stdout. */
+function completions(shell: string | undefined): void {
+  if (!shell || !isCompletionShell(shell)) {
+    throw new Error('usage: kild completions <bash|zsh|fish>');
+  }
+  process.stdout.write(completionScript(shell));
+}
+
+/** `kild __complete <worktrees|orphans|projects>` — live names for the completion
+ *  scripts, one per line. `orphans` are the `kild/*` branches with no tree (what
+ *  `worktree restore` takes). Names are the given `--project`'s, else every registered
+ *  project's. Best-effort: an unreadable project contributes nothing. */
+async function complete(what: string | undefined): Promise<void> {
+  const projects = await loadProjects();
+  if (what === 'projects') return void console.log(projects.map((p) => p.name).join('\n'));
+  if (what !== 'worktrees' && what !== 'orphans') return;
+  const repos = values.project
+    ? [(await findProject(values.project))?.path ?? values.project]
+    : projects.map((p) => p.path);
+  const names = new Set<string>();
+  for (const repo of repos) {
+    if (what === 'orphans') {
+      for (const orphan of await scanForOrphans(repo).catch(() => [])) {
+        if (orphan.kind === 'branch') names.add(orphan.name);
+      }
+      continue;
+    }
+    for (const tree of await listWorktrees(repo).catch(() => [])) {
+      if (tree.name) names.add(tree.name);
+    }
+  }
+  console.log([...names].join('\n'));
+}
+
+/** `kild ping [--count N]` — round-trip times to the engine, ping(8)-style, for telling
+ *  a slow engine (or a slow link to a remote one) from a down one. */
+async function ping(): Promise<void> {
+  const count = values.count === undefined ? 4 : Number(values.count);
+  if (!Number.isInteger(count) || count < 1) throw new Error('usage: kild ping [--count <n>]');
+  const summary = await pingEngine(count);
+  const where = isLocalEngine() ? 'local' : 'remote';
+  if (json) return void console.log(JSON.stringify({ engine: ENGINE, where, ...summary }, null, 2));
+  const lost = Math.round((100 * (summary.sent - summary.received)) / summary.sent);
+  console.log(`engine ${ENGINE} (${where})`);
+  console.log(`${summary.sent} sent, ${summary.received} received, ${lost}% lost`);
+  if (summary.received === 0) throw new Error(`engine not responding at ${ENGINE}`);
+  const ms = [summary.min, summary.avg, summary.max].map((v) => (v ?? 0).toFixed(1));
+  console.log(`rtt min/avg/max = ${ms.join('/')} ms`);
+}
+
+/** `kild whereis <name>` — just the worktree's path, undecorated, for
+ *  `cd "$(kild whereis <name> --project <p>)"`. */
+async function whereis(name: string | undefined): Promise<void> {
+  if (!name || !values.project) throw new Error('usage: kild whereis <name> --project <p>');
+  const repo = (await findProject(values.project))?.path ?? values.project;
+  const tree = await findWorktree(repo, name);
+  if (!tree) throw new Error(`no worktree named ${name} in ${repo}`);
+  if (json) return void console.log(JSON.stringify({ ...tree, repo }, null, 2));
+  console.log(tree.path);
+}
+
+/** `kild since <30m|2h|1d|1w|date> --project <p>` — catch up: the commits that landed
+ *  on each of the project's kild branches since the cutoff. */
+async function since(when: string | undefined): Promise<void> {
+  const cutoff = when ? parseSince(when) : undefined;
+  if (cutoff === undefined) {
+    throw new Error('usage: kild since <30m|2h|1d|1w|YYYY-MM-DD> --project <p> [--base <b>]');
+  }
+  if (!values.project) throw new Error('--project <name|path> is required');
+  const repo = (await findProject(values.project))?.path ?? values.project;
+  const base = await resolveBaseBranch(repo, values.base);
+  const activity = await activitySince(repo, cutoff, base);
+  if (json) return void console.log(JSON.stringify({ since: cutoff, base, activity }, null, 2));
+  if (activity.length === 0) return void console.error(`no new commits since ${when}`);
+  for (const { name, commits } of activity) {
+    console.log(`${name} (${commits.length} commit(s))`);
+    for (const c of commits) console.log(`  ${c.sha.slice(0, 7)}  ${c.subject}  (${c.author})`);
+  }
+}
+
+/** `kild config get|set|list` — the global config, or a project's with `--project`. */
+async function config(action: string | undefined, args: string[]): Promise<void> {
+  const cwd = values.project
+    ? ((await findProject(values.project))?.path ?? values.project)
+    : undefined;
+  const [key, value] = args;
+  if (action === 'list') {
+    const entries = await configEntries(cwd);
+    if (json) return void console.log(JSON.stringify(entries, null, 2));
+    if (entries.length === 0) return void console.error('no config set (defaults apply)');
+    for (const e of entries) {
+      const note = e.effective ? '' : '  (not in effect)';
+      console.log(`${e.key} = ${JSON.stringify(e.value)}  [${e.source}]${note}`);
+    }
+    return;
+  }
+  if (action === 'get' && key) {
+    const current = await effectiveConfigValue(configKey(key), cwd);
+    if (json) return void console.log(JSON.stringify({ key, value: current ?? null }, null, 2));
+    if (current === undefined) return void console.error(`${key} is not set`);
+    console.log(typeof current === 'string' ? current : JSON.stringify(current));
+  } else if (action === 'set' && key && value !== undefined) {
+    await setConfigValue(configKey(key), value, cwd);
+    if (json) console.log(JSON.stringify({ ok: true, key, file: configFile(cwd) }, null, 2));
+    else console.log(`set ${key} in ${configFile(cwd)}`);
+  } else {
+    throw new Error('usage: kild config <get <key>|set <key> <value>|list> [--project <p>]');
+  }
+}
+
+function configKey(key: string): ConfigKey {
+  if (isConfigKey(key)) return key;
+  throw new Error(`unknown config key: ${key} (one of: ${Object.keys(CONFIG_KEYS).join(', ')})`);
+}
+
+/** `kild events` — every engine event (room and session frames, as the cockpit gets
+ *  them) on stdout, one JSON object per line, until Ctrl-C or the engine goes away.
+ *  For dashboards and scripts that would otherwise poll. */
+async function events(): Promise<void> {
+  if (!(await engineRunning())) {
+    throw new Error(`engine not running at ${ENGINE} — start it: cd engine && bun run dev`);
   }
+  const ws = new WebSocket(`${ENGINE.replace(/^http/, 'ws')}/ws`);
+  await new Promise<void>((resolve, reject) => {
+    process.on('SIGINT', () => {
+      ws.close();
+      resolve();
+    });
+    // Frames are compact JSON (the engine sends `JSON.stringify(msg)`), so each is a line.
+    ws.addEventListener('message', (e) => {
+      process.stdout.write(`${String((e as { data: unknown }).data)}\n`);
+    });
+    ws.addEventListener('close', () => reject(new Error('engine closed the event stream')));
+    ws.addEventListener('error', () => reject(new Error(`cannot connect to ${ENGINE}/ws`)));
+  });
+}
+
+/** `kild sessions env <id>` — the env kild injected into a live session's worker, as
+ *  `KEY=VALUE` lines (sorted) — for debugging what an agent actually received. */
+async function sessionEnvList(id: string | undefined): Promise<void> {
+  if (!id) throw new Error('usage: kild sessions env <id>');
+  const env = await getSessionEnv(id);
+  if (json) return void console.log(JSON.stringify(env, null, 2));
+  for (const key of Object.keys(env).sort()) console.log(`${key}=${env[key]}`);
 }
 
 async function fleetInteractive(goal: string): Promise<void> {
@@ -487,6 +1139,10 @@ async function roomShow(id: string): Promise<void> {
 async function roomsList(): Promise<void> {
   const rooms = compactLiveRooms(await getLiveRooms());
   if (json) return void console.log(JSON.stringify(rooms, null, 2));
+  if (values.porcelain) {
+    for (const r of rooms) console.log(formatPorcelainRoom(r));
+    return;
+  }
   if (rooms.length === 0) return void console.error('no live rooms');
   for (const r of rooms) {
     const parts = r.participants.map((p) => (p.model ? `${p.name}:${p.model}` : p.name)).join(', ');
@@ -763,6 +1419,7 @@ async function runViaWorker(prompt: string): Promise<void> {
   let tokens = 0;
   let cost = 0;
   let buffer = '';
+  const decoder = new StringDecoder('utf8'); // a multibyte character may span two reads
   let agentEnded = false;
 
   await new Promise<void>((resolve, reject) => {
@@ -814,7 +1471,7 @@ async function runViaWorker(prompt: string): Promise<void> {
     };
 
     child.stdout?.on('data', (chunk: Buffer) => {
-      buffer += chunk.toString();
+      buffer += decoder.write(chunk);
       const lines = buffer.split('\n');
       buffer = lines.pop() ?? '';
       for (const raw of lines) {
@@ -0,0 +1,93 @@
+import { afterEach, beforeEach, expect, test } from 'bun:test';
+import { execFile as execFileCb } from 'node:child_process';
+import { existsSync, mkdtempSync, rmSync, writeFileSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+
+import { archiveBranch, archivesRoot, parseBundleHeads, restoreArchive } from './archive.ts';
+
+const execFile = promisify(execFileCb);
+
+let repo: string;
+let home: string;
+let prevHome: string | undefined;
+
+const git = (...args: string[]) => execFile('git', ['-C', repo, ...args]);
+
+beforeEach(async () => {
+  repo = mkdtempSync(path.join(tmpdir(), 'kild-archive-repo-'));
+  home = mkdtempSync(path.join(tmpdir(), 'kild-archive-home-'));
+  prevHome = process.env.KILD_HOME;
+  process.env.KILD_HOME = home; // archives live under $KILD_HOME/archives
+  await git('init', '-q', '-b', 'main');
+  await git('config', 'user.email', 't@t');
+  await git('config', 'user.name', 't');
+  await git('commit', '-q', '--allow-empty', '-m', 'init');
+});
+
+afterEach(() => {
+  if (prevHome === undefined) delete process.env.KILD_HOME;
+  else process.env.KILD_HOME = prevHome;
+  rmSync(repo, { recursive: true, force: true });
+  rmSync(home, { recursive: true, force: true });
+});
+
+test('a branch with its own commits is archived as a verifiable bundle', async () => {
+  await git('branch', 'kild/feat');
+  await git('checkout', '-q', 'kild/feat');
+  await git('commit', '-q', '--allow-empty', '-m', 'unique');
+  await git('checkout', '-q', 'main');
+
+  const file = await archiveBranch(repo, 'kild/feat', 'main');
+
+  expect(file).toBeDefined();
+  expect(path.dirname(file as string)).toBe(archivesRoot());
+  expect(path.basename(file as string)).toStartWith('kild-feat-');
+  await git('bundle', 'verify', file as string); // throws if the bundle is unusable
+});
+
+test('a branch with nothing beyond base is not archived', async () => {
+  await git('branch', 'kild/empty');
+
+  expect(await archiveBranch(repo, 'kild/empty', 'main')).toBeUndefined();
+  expect(existsSync(archivesRoot())).toBe(false);
+});
+
+test('bundle heads are the branch refs; HEAD and tags are skipped', () => {
+  const stdout = [
+    'a1b2c3 refs/heads/kild/feat',
+    'a1b2c3 HEAD',
+    'd4e5f6 refs/tags/v1',
+    '',
+  ].join('\n');
+
+  expect(parseBundleHeads(stdout)).toEqual(['kild/feat']);
+});
+
+test('an archived branch is restored with its commits', async () => {
+  await git('branch', 'kild/feat');
+  await git('checkout', '-q', 'kild/feat');
+  await git('commit', '-q', '--allow-empty', '-m', 'one');
+  await git('commit', '-q', '--allow-empty', '-m', 'two');
+  await git('checkout', '-q', 'main');
+  const file = (await archiveBranch(repo, 'kild/feat', 'main')) as string;
+  await git('branch', '-D', 'kild/feat');
+
+  expect(await restoreArchive(repo, file)).toEqual({ branch: 'kild/feat', commits: 2 });
+  expect((await git('log', '-1', '--format=%s', 'kild/feat')).stdout.trim()).toBe('two');
+});
+
+test('a corrupt bundle is rejected; an existing branch is never overwritten', async () => {
+  const junk = path.join(home, 'junk.bundle');
+  writeFileSync(junk, 'not a bundle\n');
+  await expect(restoreArchive(repo, junk)).rejects.toThrow('not a valid bundle');
+
+  await git('branch', 'kild/feat');
+  await git('checkout', '-q', 'kild/feat');
+  await git('commit', '-q', '--allow-empty', '-m', 'unique');
+  await git('checkout', '-q', 'main');
+  const file = (await archiveBranch(repo, 'kild/feat', 'main')) as string;
+
+  await expect(restoreArchive(repo, file)).rejects.toThrow('branch already exists: kild/feat');
+});
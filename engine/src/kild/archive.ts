@@ -0,0 +1,85 @@
+import { execFile as execFileCb } from 'node:child_process';
+import { mkdirSync } from 'node:fs';
+import path from 'node:path';
+import { promisify } from 'node:util';
+
+import { kildHome } from './config.ts';
+
+const execFile = promisify(execFileCb);
+
+const errText = (err: unknown): string => (err instanceof Error ? err.message : String(err));
+
+/** Where branch archives live: `$KILD_HOME/archives`. */
+export function archivesRoot(): string {
+  return path.join(kildHome(), 'archives');
+}
+
+/**
+ * Archive the commits `branch` has that `base` doesn't as a `git bundle` under
+ * {@link archivesRoot} (`<branch>-<timestamp>.bundle`, `/` flattened to `-`) — a
+ * safety net before a tree or branch is removed, recoverable with {@link restoreArchive}.
+ * Returns the bundle path, or undefined when the branch has no commits of its own
+ * (there is nothing to lose).
+ */
+export async function archiveBranch(
+  repo: string,
+  branch: string,
+  base: string,
+): Promise<string | undefined> {
+  const count = await execFile('git', ['-C', repo, 'rev-list', '--count', `${base}..${branch}`]);
+  if (Number.parseInt(count.stdout, 10) === 0) return undefined;
+  mkdirSync(archivesRoot(), { recursive: true });
+  const stamp = new Date().toISOString().replace(/[:.]/g, '-');
+  const file = path.join(archivesRoot(), `${branch.replace(/\//g, '-')}-${stamp}.bundle`);
+  // `^base` limits the bundle to the branch's own commits; the bundle then requires
+  // base's history to be present where it is unbundled — the repo it came from.
+  await execFile('git', ['-C', repo, 'bundle', 'create', file, branch, `^${base}`]);
+  return file;
+}
+
+/** The branches a bundle carries, from `git bundle list-heads` (`<sha> <ref>` lines);
+ *  non-branch refs (HEAD, tags) are skipped. */
+export function parseBundleHeads(stdout: string): string[] {
+  const branches: string[] = [];
+  for (const line of stdout.split('\n')) {
+    const ref = line.trim().split(/\s+/)[1];
+    if (ref?.startsWith('refs/heads/')) branches.push(ref.slice('refs/heads/'.length));
+  }
+  return branches;
+}
+
+/** Import the branch archived in `bundle` (made by {@link archiveBranch}) back into
+ *  `repo`. The bundle is verified first — a corrupt file, or one whose base history
+ *  this repo lacks, is rejected before anything is written — and an existing branch of
+ *  the same name is never overwritten. Returns the branch and how many of its commits
+ *  no other local branch has. */
+export async function restoreArchive(
+  repo: string,
+  bundle: string,
+): Promise<{ branch: string; commits: number }> {
+  const file = path.resolve(bundle); // git runs in `repo`; a relative path means the caller's cwd
+  await execFile('git', ['-C', repo, 'bundle', 'verify', '--quiet', file]).catch((err) => {
+    throw new Error(`not a valid bundle for this repo: ${bundle} (${errText(err)})`);
+  });
+  const heads = await execFile('git', ['-C', repo, 'bundle', 'list-heads', file]);
+  const [branch] = parseBundleHeads(heads.stdout);
+  if (!branch) throw new Error(`bundle has no branch: ${bundle}`);
+  const ref = `refs/heads/${branch}`;
+  const exists = await execFile('git', ['-C', repo, 'rev-parse', '--verify', '--quiet', ref])
+    .then(() => true)
+    .catch(() => false);
+  if (exists) throw new Error(`branch already exists: ${branch}`);
+
+  await execFile('git', ['-C', repo, 'fetch', '--quiet', file, `${ref}:${ref}`]);
+  const count = await execFile('git', [
+    '-C',
+    repo,
+    'rev-list',
+    '--count',
+    ref,
+    '--not',
+    `--exclude=${ref}`,
+    '--branches',
+  ]);
+  return { branch, commits: Number.parseInt(count.stdout, 10) || 0 };
+}
@@ -0,0 +1,31 @@
+import { expect, test } from 'bun:test';
+
+import { COMMAND_TREE, completionScript, isCompletionShell } from './completions.ts';
+
+test('the bash script completes every top-level command and registers itself', () => {
+  const script = completionScript('bash');
+  for (const cmd of Object.keys(COMMAND_TREE)) expect(script).toContain(cmd);
+  expect(script).toContain('worktree) words="ls add status log');
+  expect(script).toContain('kild __complete worktrees');
+  expect(script).toEndWith('complete -F _kild kild\n');
+});
+
+test('zsh reuses the bash function; fish gets native complete lines', () => {
+  const zsh = completionScript('zsh');
+  expect(zsh).toContain('bashcompinit');
+  expect(zsh).toContain('_kild() {');
+  expect(completionScript('fish')).toMatch(
+    /complete -c kild -n '__fish_seen_subcommand_from config; and not .*' -a 'get set list'/,
+  );
+  expect(isCompletionShell('fish')).toBe(true);
+  expect(isCompletionShell('powershell')).toBe(false);
+});
+
+test('restore completes from orphaned kild branches, not live worktrees', () => {
+  const bash = completionScript('bash');
+  expect(bash).toContain('$sub == restore ]]; then\n    words=$(kild __complete orphans');
+  expect(bash).not.toMatch(/" [^"]*\brestore\b[^"]* " == \*/);
+  expect(completionScript('fish')).toContain(
+    "__fish_seen_subcommand_from restore' -a '(kild __complete orphans",
+  );
+});
@@ -0,0 +1,156 @@
+/**
+ * Shell completion for the kild CLI. The command tree is static and baked into the
+ * script; worktree, orphaned-branch and project names are live — the script calls back into
+ * `kild __complete <worktrees|orphans|projects>` (see the CLI) each time it needs them.
+ */
+
+/** Each top-level command and the subcommands it takes (none: free arguments). */
+export const COMMAND_TREE: Record<string, string[]> = {
+  project: ['ls', 'add', 'rm'],
+  agent: ['ls', 'show'],
+  worktree: [
+    'ls',
+    'add',
+    'status',
+    'log',
+    'diff',
+    'pr-body',
+    'pr',
+    'rm',
+    'mv',
+    'fork',
+    'rebase',
+    'restore',
+    'restore-archive',
+    'prune',
+    'cleanup',
+    'adopt',
+  ],
+  run: [],
+  room: ['ls', 'open', 'log', 'show', 'post', 'close'],
+  rooms: [],
+  fleet: ['ls', 'post', 'stop'],
+  sessions: ['env'],
+  since: [],
+  events: [],
+  config: ['get', 'set', 'list'],
+  whereis: [],
+  completions: ['bash', 'zsh', 'fish'],
+  ping: [],
+};
+
+/** `worktree` subcommands whose first argument is an existing worktree's name. (`restore`
+ *  takes a removed one's — it completes from the orphaned `kild/*` branches instead.) */
+const NAMED_WORKTREE_ACTIONS = [
+  'status',
+  'log',
+  'diff',
+  'pr-body',
+  'pr',
+  'rm',
+  'mv',
+  'fork',
+  'rebase',
+];
+
+/** Options besides `--project` that take a value — skipped along with it when finding
+ *  the positional words. */
+const VALUE_OPTIONS = [
+  'agent',
+  'model',
+  'worktree',
+  'participants',
+  'base',
+  'title',
+  'idle',
+  'prompt',
+];
+
+export type CompletionShell = 'bash' | 'zsh' | 'fish';
+
+export function isCompletionShell(shell: string): shell is CompletionShell {
+  return shell === 'bash' || shell === 'zsh' || shell === 'fish';
+}
+
+function bashScript(): string {
+  const named = NAMED_WORKTREE_ACTIONS.join(' ');
+  const subcommands = Object.entries(COMMAND_TREE)
+    .filter(([, subs]) => subs.length > 0)
+    .map(([cmd, subs]) => `      ${cmd}) words="${subs.join(' ')}" ;;`);
+  const valueOptions = VALUE_OPTIONS.map((o) => `--${o}`).join('|');
+  return `# kild completion — eval "$(kild completions bash)" in ~/.bashrc
+_kild() {
+  local cur=\${COMP_WORDS[COMP_CWORD]} prev=\${COMP_WORDS[COMP_CWORD-1]}
+  local args=() project=() words="" i
+  for ((i = 1; i < COMP_CWORD; i++)); do
+    case \${COMP_WORDS[i]} in
+      --project) project=(--project "\${COMP_WORDS[i+1]}"); ((i++)) ;;
+      ${valueOptions}) ((i++)) ;;
+      -*) ;;
+      *) args+=("\${COMP_WORDS[i]}") ;;
+    esac
+  done
+  local cmd=\${args[0]} sub=\${args[1]}
+  if [[ $prev == --project ]]; then
+    words=$(kild __complete projects 2>/dev/null)
+  elif ((\${#args[@]} == 0)); then
+    words="${Object.keys(COMMAND_TREE).join(' ')}"
+  elif ((\${#args[@]} == 1)) && [[ $cmd == whereis ]]; then
+    words=$(kild __complete worktrees "\${project[@]}" 2>/dev/null)
+  elif ((\${#args[@]} == 1)); then
+    case $cmd in
+${subcommands.join('\n')}
+    esac
+  elif ((\${#args[@]} == 2)) && [[ $cmd == worktree && " ${named} " == *" $sub "* ]]; then
+    words=$(kild __complete worktrees "\${project[@]}" 2>/dev/null)
+  elif ((\${#args[@]} == 2)) && [[ $cmd == worktree && $sub == restore ]]; then
+    words=$(kild __complete orphans "\${project[@]}" 2>/dev/null)
+  fi
+  COMPREPLY=($(compgen -W "$words" -- "$cur"))
+}
+complete -F _kild kild
+`;
+}
+
+function fishScript(): string {
+  const top = Object.keys(COMMAND_TREE);
+  const lines = [
+    '# kild completion — kild completions fish > ~/.config/fish/completions/kild.fish',
+    'function __kild_project',
+    '    set -l tokens (commandline -opc)',
+    '    set -l i (contains -i -- --project $tokens)',
+    '    and printf "%s\\n" --project $tokens[(math $i + 1)]',
+    'end',
+    'complete -c kild -f',
+    `complete -c kild -n __fish_use_subcommand -a '${top.join(' ')}'`,
+  ];
+  for (const [cmd, subs] of Object.entries(COMMAND_TREE)) {
+    if (subs.length === 0) continue;
+    const seen = `__fish_seen_subcommand_from ${cmd}`;
+    const fresh = `not __fish_seen_subcommand_from ${subs.join(' ')}`;
+    lines.push(`complete -c kild -n '${seen}; and ${fresh}' -a '${subs.join(' ')}'`);
+  }
+  const worktrees = `'(kild __complete worktrees (__kild_project) 2>/dev/null)'`;
+  const orphans = `'(kild __complete orphans (__kild_project) 2>/dev/null)'`;
+  const named = `__fish_seen_subcommand_from ${NAMED_WORKTREE_ACTIONS.join(' ')}`;
+  const restore = '__fish_seen_subcommand_from restore';
+  lines.push(
+    `complete -c kild -n '__fish_seen_subcommand_from worktree; and ${named}' -a ${worktrees}`,
+    `complete -c kild -n '__fish_seen_subcommand_from worktree; and ${restore}' -a ${orphans}`,
+    `complete -c kild -n '__fish_seen_subcommand_from whereis' -a ${worktrees}`,
+    `complete -c kild -l project -r -a '(kild __complete projects 2>/dev/null)'`,
+  );
+  return `${lines.join('\n')}\n`;
+}
+
+/** The completion script for `shell`. zsh reuses the bash function through
+ *  `bashcompinit`, so the two can't drift apart. */
+export function completionScript(shell: CompletionShell): string {
+  if (shell === 'fish') return fishScript();
+  if (shell === 'zsh') {
+    return `# kild completion — eval "$(kild completions zsh)" in ~/.zshrc
+autoload -U +X bashcompinit && bashcompinit
+${bashScript().split('\n').slice(1).join('\n')}`;
+  }
+  return bashScript();
+}
@@ -3,7 +3,19 @@ import fs from 'node:fs';
 import os from 'node:os';
 import path from 'node:path';
 
-import { configuredMemoryDir, resolvePluginPaths } from './config.ts';
+import {
+  configEntries,
+  configEnvVar,
+  configuredBaseBranch,
+  configuredMemoryDir,
+  configuredWorktreeRoot,
+  effectiveConfigValue,
+  envConfig,
+  parseConfigValue,
+  resolvePluginPaths,
+  setConfigValue,
+} from './config.ts';
+import { worktreePath, worktreesRoot } from './worktree.ts';
 
 let tmp: string;
 let prevHome: string | undefined;
@@ -101,3 +113,129 @@ test('missing or malformed config yields nothing and never throws', async () =>
   expect(await resolvePluginPaths(proj)).toEqual({ agentDirs: [], skillDirs: [] });
   expect(await configuredMemoryDir(proj)).toBe(path.join(proj, '.kild')); // falls back to default
 });
+
+test('worktreeRoot moves new worktree paths; unset keeps $KILD_HOME/worktrees', () => {
+  const home = process.env.KILD_HOME as string;
+  expect(configuredWorktreeRoot()).toBeUndefined();
+  expect(worktreesRoot()).toBe(path.join(home, 'worktrees'));
+
+  fs.writeFileSync(path.join(home, 'config.json'), JSON.stringify({ worktreeRoot: '/fast/trees' }));
+  expect(worktreesRoot()).toBe('/fast/trees');
+  expect(worktreePath('feat/x')).toBe(path.join('/fast/trees', 'feat%2Fx'));
+
+  fs.writeFileSync(path.join(home, 'config.json'), JSON.stringify({ worktreeRoot: 'trees' }));
+  expect(configuredWorktreeRoot()).toBe(path.join(home, 'trees'));
+});
+
+test('a project worktreeRoot wins over the global one for that project only', async () => {
+  const proj = path.join(tmp, 'proj');
+  await setConfigValue('worktreeRoot', '/fast/trees');
+  await setConfigValue('worktreeRoot', 'trees', proj); // relative: under the project
+
+  expect(worktreesRoot(proj)).toBe(path.join(proj, 'trees'));
+  expect(worktreePath('feat/x', proj)).toBe(path.join(proj, 'trees', 'feat%2Fx'));
+  expect(worktreesRoot(path.join(tmp, 'other'))).toBe('/fast/trees');
+  expect(worktreesRoot()).toBe('/fast/trees');
+  expect(await configEntries(proj)).toContainEqual({
+    key: 'worktreeRoot',
+    value: 'trees',
+    source: 'project',
+    effective: true,
+  });
+
+  try {
+    process.env.KILD_WORKTREE_ROOT = '/env/trees';
+    expect(worktreesRoot(proj)).toBe('/env/trees');
+  } finally {
+    delete process.env.KILD_WORKTREE_ROOT;
+  }
+});
+
+test('config set writes a typed value that get reads back, keeping other keys', async () => {
+  const file = path.join(process.env.KILD_HOME as string, 'config.json');
+  fs.writeFileSync(file, JSON.stringify({ models: { 'a/b': 'fast' } }));
+
+  await setConfigValue('dirtyIgnoresUntracked', 'true');
+  await setConfigValue('memory.synthesis.model', 'a/b');
+  await setConfigValue('cleanIgnoreGlobs', '["*.local.md"]');
+
+  expect(await effectiveConfigValue('dirtyIgnoresUntracked')).toBe(true);
+  expect(await effectiveConfigValue('memory.synthesis.model')).toBe('a/b');
+  expect(await effectiveConfigValue('cleanIgnoreGlobs')).toEqual(['*.local.md']);
+  expect(JSON.parse(fs.readFileSync(file, 'utf8')).models).toEqual({ 'a/b': 'fast' });
+});
+
+test('config values are validated against the key type', async () => {
+  expect(() => parseConfigValue('dirtyIgnoresUntracked', 'yes')).toThrow('true or false');
+  expect(() => parseConfigValue('cleanIgnoreGlobs', '*.md')).toThrow('JSON array of strings');
+  expect(() => parseConfigValue('cleanIgnoreGlobs', '[1]')).toThrow('JSON array of strings');
+  expect(() => parseConfigValue('baseBranch', '')).toThrow('non-empty');
+});
+
+test('config list reports each layer; a project value overrides, list keys append', async () => {
+  await setConfigValue('baseBranch', 'main');
+  await setConfigValue('skillPaths', '["/global"]');
+  const proj = path.join(tmp, 'proj');
+  await setConfigValue('baseBranch', 'dev', proj);
+  await setConfigValue('skillPaths', '["./local"]', proj);
+
+  const entries = await configEntries(proj);
+  expect(entries).toContainEqual({
+    key: 'baseBranch',
+    value: 'main',
+    source: 'global',
+    effective: false,
+  });
+  expect(await effectiveConfigValue('baseBranch', proj)).toBe('dev');
+  expect(await effectiveConfigValue('skillPaths', proj)).toEqual(['/global', './local']);
+  expect(await effectiveConfigValue('baseBranch')).toBe('main');
+});
+
+test('config keys map to KILD_ env var names deterministically', () => {
+  expect(configEnvVar('baseBranch')).toBe('KILD_BASE_BRANCH');
+  expect(configEnvVar('memory.synthesis.model')).toBe('KILD_MEMORY_SYNTHESIS_MODEL');
+  expect(configEnvVar('worktreeRoot')).toBe('KILD_WORKTREE_ROOT');
+});
+
+test('an env var overrides the config files; unset or empty leaves them in charge', async () => {
+  const proj = path.join(tmp, 'proj');
+  writeProjectConfig(proj, { baseBranch: 'dev' });
+  try {
+    process.env.KILD_BASE_BRANCH = 'ci';
+    expect(await configuredBaseBranch(proj)).toBe('ci');
+    expect(await effectiveConfigValue('baseBranch', proj)).toBe('ci');
+    expect(await configEntries(proj)).toContainEqual({
+      key: 'baseBranch',
+      value: 'dev',
+      source: 'project',
+      effective: false,
+    });
+
+    process.env.KILD_BASE_BRANCH = '';
+    expect(await configuredBaseBranch(proj)).toBe('dev');
+    delete process.env.KILD_BASE_BRANCH;
+    expect(await configuredBaseBranch(proj)).toBe('dev');
+  } finally {
+    delete process.env.KILD_BASE_BRANCH;
+  }
+});
+
+test('a malformed env var is ignored and warned about once per value, not once per read', () => {
+  const warned: unknown[] = [];
+  const warn = console.warn;
+  console.warn = (...args: unknown[]) => warned.push(args.join(' '));
+  try {
+    process.env.KILD_DIRTY_IGNORES_UNTRACKED = 'maybe';
+    expect(envConfig()).toEqual({});
+    expect(envConfig()).toEqual({});
+    expect(warned).toHaveLength(1);
+    expect(String(warned[0])).toContain('KILD_DIRTY_IGNORES_UNTRACKED');
+
+    process.env.KILD_DIRTY_IGNORES_UNTRACKED = 'perhaps';
+    envConfig();
+    expect(warned).toHaveLength(2);
+  } finally {
+    console.warn = warn;
+    delete process.env.KILD_DIRTY_IGNORES_UNTRACKED;
+  }
+});
@@ -1,3 +1,4 @@
+import { readFileSync } from 'node:fs';
 import fs from 'node:fs/promises';
 import path from 'node:path';
 
@@ -14,7 +15,8 @@ export function kildHome(): string {
 }
 
 /**
- * kild config (`.kild/config.json` in a project, and/or `$KILD_HOME/config.json` global).
+ * kild config (`.kild/config.json` in a project, and/or `$KILD_HOME/config.json` global,
+ * with `KILD_*` env overrides on top — see {@link configEnvVar}).
  * This is how you plug in a framework: a `plugins:` entry points at a dir laid out like a
  * Claude Code plugin (`agents/` + `skills/`), and kild discovers both. kild ships no roles
  * or process of its own — this is where the project brings them.
@@ -30,6 +32,18 @@ export interface KildConfig {
    *  measured against (e.g. `dev`). Overridable per-invocation with `--base`; if unset,
    *  the checkout's current branch is used. */
   baseBranch?: string;
+  /** Leave untracked files out of the "dirty" signal: git status reports only tracked
+   *  changes, and `worktree rm` removes a tree whose only changes are untracked files
+   *  (build output, say) without `--force`. `.gitignore`d files never count either way. */
+  dirtyIgnoresUntracked?: boolean;
+  /** Paths whose uncommitted changes never count as dirty — always-present local-only
+   *  files (`*.local.md`, `.env/*`). Glob syntax in glob.ts; `**` spans directories. */
+  cleanIgnoreGlobs?: string[];
+  /** Where worktrees are created (default `$KILD_HOME/worktrees`) — e.g. a faster or
+   *  larger volume. A project's value overrides the global one for that project's new
+   *  trees. `~` expands; relative is under $KILD_HOME (global) or the project dir.
+   *  Existing trees stay where they are (git tracks them by absolute path). */
+  worktreeRoot?: string;
   /** Preferred models for delegation: `provider/model` ref → a short description (what
    *  it's good at, cost). Appended to a delegating session's system prompt so the user
    *  and the orchestrator can steer which models fan-out agents run on. Order = preference. */
@@ -77,17 +91,18 @@ async function readConfigFile(file: string): Promise<KildConfig | null> {
  * Resolve agent + skill dirs from the global (`$KILD_HOME/config.json`) and project
  * (`<cwd>/.kild/config.json`) configs. A `plugins:` dir contributes `<dir>/agents` and
  * `<dir>/skills`; `agentPaths`/`skillPaths` add explicit dirs. Every path resolves
- * relative to the config file that declared it. Never throws — bad config yields nothing.
+ * relative to the config file that declared it (an env override's, against `cwd`).
+ * Never throws — bad config yields nothing.
  */
 export async function resolvePluginPaths(cwd: string): Promise<ResolvedPluginPaths> {
   const sources = [
-    { file: path.join(kildHome(), 'config.json'), base: kildHome() },
-    { file: path.join(cwd, '.kild', 'config.json'), base: cwd },
+    { cfg: await readConfigFile(path.join(kildHome(), 'config.json')), base: kildHome() },
+    { cfg: await readConfigFile(path.join(cwd, '.kild', 'config.json')), base: cwd },
+    { cfg: envConfig(), base: cwd },
   ];
   const agentDirs: string[] = [];
   const skillDirs: string[] = [];
-  for (const { file, base } of sources) {
-    const cfg = await readConfigFile(file);
+  for (const { cfg, base } of sources) {
     if (!cfg) continue;
     // Absolute (or `~/…`) paths load from anywhere on the system; relative paths resolve
     // against the config file that declared them.
@@ -102,16 +117,63 @@ export async function resolvePluginPaths(cwd: string): Promise<ResolvedPluginPat
   return { agentDirs, skillDirs };
 }
 
-/** The configured base branch for `cwd`: project (`<cwd>/.kild/config.json`) over global
- *  (`$KILD_HOME/config.json`). Undefined when neither sets `baseBranch` — the caller then
- *  falls back to the checkout's current branch. Never throws. */
+/** The configured base branch for `cwd`: `KILD_BASE_BRANCH`, else project
+ *  (`<cwd>/.kild/config.json`) over global (`$KILD_HOME/config.json`). Undefined when none
+ *  sets it — the caller then falls back to the checkout's current branch. Never throws. */
 export async function configuredBaseBranch(cwd: string): Promise<string | undefined> {
+  const env = envConfig().baseBranch;
+  if (env) return env;
   const project = await readConfigFile(path.join(cwd, '.kild', 'config.json'));
   if (project?.baseBranch) return project.baseBranch;
   const global = await readConfigFile(path.join(kildHome(), 'config.json'));
   return global?.baseBranch;
 }
 
+/** `KILD_WORKTREE_ROOT`, else `repo`'s project `worktreeRoot` (when a repo is given),
+ *  else the global one; undefined when none sets it. A relative value resolves against
+ *  $KILD_HOME, or the project dir for a project's. Synchronous — worktree paths are
+ *  derived synchronously (see `worktreePath`). Never throws. */
+export function configuredWorktreeRoot(repo?: string): string | undefined {
+  const read = (file: string): KildConfig | null => {
+    try {
+      return JSON.parse(readFileSync(file, 'utf8')) as KildConfig;
+    } catch {
+      return null; // missing or malformed config falls back to the next layer
+    }
+  };
+  const layers = [
+    { root: envConfig().worktreeRoot, base: kildHome() },
+    { root: repo ? read(configFile(repo))?.worktreeRoot : undefined, base: repo ?? '' },
+    { root: read(configFile())?.worktreeRoot, base: kildHome() },
+  ];
+  const set = layers.find(({ root }) => typeof root === 'string' && root);
+  return set ? path.resolve(set.base, expandHome(set.root as string)) : undefined;
+}
+
+/** What counts as uncommitted work when deciding whether a tree is dirty. */
+export interface DirtyPolicy {
+  ignoreUntracked: boolean;
+  ignoreGlobs: string[];
+}
+
+/** The dirty policy for `cwd`: `dirtyIgnoresUntracked` + `cleanIgnoreGlobs`, each env
+ *  over project over global. Defaults count every change — an untracked or local-only
+ *  file is work until the user says otherwise. Never throws. */
+export async function configuredDirtyPolicy(cwd: string): Promise<DirtyPolicy> {
+  const env = envConfig();
+  const project = await readConfigFile(path.join(cwd, '.kild', 'config.json'));
+  const global = await readConfigFile(path.join(kildHome(), 'config.json'));
+  return {
+    ignoreUntracked:
+      env.dirtyIgnoresUntracked ??
+      project?.dirtyIgnoresUntracked ??
+      global?.dirtyIgnoresUntracked ??
+      false,
+    ignoreGlobs:
+      env.cleanIgnoreGlobs ?? project?.cleanIgnoreGlobs ?? global?.cleanIgnoreGlobs ?? [],
+  };
+}
+
 /** The configured model catalog for `cwd` (`provider/model` → description), merged
  *  global < project (project wins per key). Empty when none configured. Never throws. */
 export async function configuredModels(cwd: string): Promise<Record<string, string>> {
@@ -120,21 +182,204 @@ export async function configuredModels(cwd: string): Promise<Record<string, stri
   return { ...(global?.models ?? {}), ...(project?.models ?? {}) };
 }
 
-/** The resolved memory directory for `cwd`: config `memory.dir`, project over global,
- *  default `.kild`. `~` expands to $HOME; a relative value resolves against `cwd`.
+/** The resolved memory directory for `cwd`: config `memory.dir`, env over project over
+ *  global, default `.kild`. `~` expands to $HOME; a relative value resolves against `cwd`.
  *  Always returns an absolute path. Never throws. */
 export async function configuredMemoryDir(cwd: string): Promise<string> {
   const project = await readConfigFile(path.join(cwd, '.kild', 'config.json'));
   const global = await readConfigFile(path.join(kildHome(), 'config.json'));
-  const dir = project?.memory?.dir ?? global?.memory?.dir ?? '.kild';
+  const dir = envConfig().memory?.dir ?? project?.memory?.dir ?? global?.memory?.dir ?? '.kild';
   return path.resolve(cwd, expandHome(dir));
 }
 
-/** Merged memory-synthesis config (project wins over global); undefined = synthesis off. */
+/** Merged memory-synthesis config (project wins over global, and `KILD_MEMORY_SYNTHESIS_*`
+ *  fields over both); undefined = synthesis off. */
 export async function configuredMemorySynthesis(
   cwd: string,
 ): Promise<{ model?: string; agent?: string } | undefined> {
   const global = await readConfigFile(path.join(kildHome(), 'config.json'));
   const project = await readConfigFile(path.join(cwd, '.kild', 'config.json'));
-  return project?.memory?.synthesis ?? global?.memory?.synthesis;
+  const file = project?.memory?.synthesis ?? global?.memory?.synthesis;
+  const env = envConfig().memory?.synthesis;
+  return env ? { ...file, ...env } : file;
+}
+
+type ConfigValueType = 'string' | 'boolean' | 'string[]';
+
+/** The keys `kild config` reads and writes (nested ones dotted), with their types.
+ *  `models` is a free-form map and stays a hand edit. */
+export const CONFIG_KEYS = {
+  plugins: 'string[]',
+  agentPaths: 'string[]',
+  skillPaths: 'string[]',
+  baseBranch: 'string',
+  dirtyIgnoresUntracked: 'boolean',
+  cleanIgnoreGlobs: 'string[]',
+  worktreeRoot: 'string',
+  'memory.dir': 'string',
+  'memory.synthesis.model': 'string',
+  'memory.synthesis.agent': 'string',
+} as const satisfies Record<string, ConfigValueType>;
+
+export type ConfigKey = keyof typeof CONFIG_KEYS;
+
+/** Keys whose global and project values both apply (dirs are collected from each). */
+const APPENDING_KEYS: ReadonlySet<ConfigKey> = new Set(['plugins', 'agentPaths', 'skillPaths']);
+
+export function isConfigKey(key: string): key is ConfigKey {
+  return Object.hasOwn(CONFIG_KEYS, key);
+}
+
+/** Parse a command-line value for `key`: a boolean is `true`/`false`, a list is a JSON
+ *  array of strings, a string must be non-empty. Throws naming the expected form. */
+export function parseConfigValue(key: ConfigKey, raw: string): string | boolean | string[] {
+  const type = CONFIG_KEYS[key];
+  if (type === 'boolean') {
+    if (raw === 'true' || raw === 'false') return raw === 'true';
+    throw new Error(`${key} takes true or false, got: ${raw}`);
+  }
+  if (type === 'string[]') {
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(raw);
+    } catch {
+      parsed = undefined;
+    }
+    if (Array.isArray(parsed) && parsed.every((v) => typeof v === 'string')) return parsed;
+    throw new Error(`${key} takes a JSON array of strings, e.g. '["a","b"]'`);
+  }
+  if (!raw) throw new Error(`${key} takes a non-empty string`);
+  return raw;
+}
+
+/** The value at dotted `key` in `cfg`; undefined when unset. */
+export function getConfigValue(cfg: KildConfig, key: ConfigKey): unknown {
+  let node: unknown = cfg;
+  for (const part of key.split('.')) {
+    if (!node || typeof node !== 'object') return undefined;
+    node = (node as Record<string, unknown>)[part];
+  }
+  return node;
+}
+
+/** Set dotted `key` in `cfg`, creating intermediate objects. Throws if one is taken by a
+ *  non-object. */
+function setPath(cfg: Record<string, unknown>, key: string, value: unknown): void {
+  const parts = key.split('.');
+  let node = cfg;
+  for (const part of parts.slice(0, -1)) {
+    const next = node[part] ?? {};
+    if (typeof next !== 'object' || Array.isArray(next)) {
+      throw new Error(`${part} is not an object`);
+    }
+    node[part] = next;
+    node = next as Record<string, unknown>;
+  }
+  node[parts[parts.length - 1] as string] = value;
+}
+
+/** The env var that overrides config `key`: `KILD_` + the key in SCREAMING_SNAKE, dots as
+ *  `_` — `baseBranch` → `KILD_BASE_BRANCH`, `memory.synthesis.model` →
+ *  `KILD_MEMORY_SYNTHESIS_MODEL`. For CI and scripts, where writing a config file is
+ *  awkward. Precedence: env > project > global > default. */
+export function configEnvVar(key: ConfigKey): string {
+  return `KILD_${key.replace(/([a-z])([A-Z])/g, '$1_$2').replace(/\./g, '_').toUpperCase()}`;
+}
+
+// `var=value` pairs already warned about. envConfig runs on every config read, so
+// without this one bad variable would warn once per read.
+const warnedEnv = new Set<string>();
+
+/** The env override layer: each {@link CONFIG_KEYS} key whose env var is set and
+ *  non-empty, parsed like a `kild config set` value. A malformed value is ignored with
+ *  a warning rather than failing every config read — once per process for each
+ *  variable and value. */
+export function envConfig(): KildConfig {
+  const cfg: Record<string, unknown> = {};
+  for (const key of Object.keys(CONFIG_KEYS) as ConfigKey[]) {
+    const raw = process.env[configEnvVar(key)];
+    if (!raw) continue;
+    try {
+      setPath(cfg, key, parseConfigValue(key, raw));
+    } catch (err) {
+      const seen = `${configEnvVar(key)}=${raw}`;
+      if (warnedEnv.has(seen)) continue;
+      warnedEnv.add(seen);
+      const reason = err instanceof Error ? err.message : err;
+      console.warn(`kild: ignoring ${configEnvVar(key)}: ${reason}`);
+    }
+  }
+  return cfg as KildConfig;
+}
+
+/** The config file `kild config set` writes: the project's `.kild/config.json` when a
+ *  project dir is given, else the global `$KILD_HOME/config.json`. */
+export function configFile(cwd?: string): string {
+  return cwd ? path.join(cwd, '.kild', 'config.json') : path.join(kildHome(), 'config.json');
+}
+
+/** Set `key` to `raw` (parsed per its type) in {@link configFile}, keeping every other
+ *  key. Creates the file if missing; a malformed one is an error, never overwritten. */
+export async function setConfigValue(key: ConfigKey, raw: string, cwd?: string): Promise<void> {
+  const file = configFile(cwd);
+  const value = parseConfigValue(key, raw);
+  let cfg: Record<string, unknown> = {};
+  try {
+    cfg = JSON.parse(await fs.readFile(file, 'utf8'));
+  } catch (err) {
+    if ((err as NodeJS.ErrnoException).code !== 'ENOENT') {
+      throw new Error(`cannot update ${file}: ${err instanceof Error ? err.message : err}`);
+    }
+  }
+  if (!cfg || typeof cfg !== 'object' || Array.isArray(cfg)) {
+    throw new Error(`cannot update ${file}: not a JSON object`);
+  }
+  try {
+    setPath(cfg, key, value);
+  } catch (err) {
+    throw new Error(`cannot update ${file}: ${err instanceof Error ? err.message : err}`);
+  }
+  await fs.mkdir(path.dirname(file), { recursive: true });
+  await fs.writeFile(file, `${JSON.stringify(cfg, null, 2)}\n`);
+}
+
+/** One set config value and the layer it was read from. `effective` is false when a
+ *  later layer overrides the value. */
+export interface ConfigEntry {
+  key: ConfigKey;
+  value: unknown;
+  source: 'global' | 'project' | 'env';
+  effective: boolean;
+}
+
+/** Every set config key, layer by layer in precedence order: global, then the project
+ *  (when `cwd` is given), then env. Unset keys are omitted (their built-in default
+ *  applies). Never throws. */
+export async function configEntries(cwd?: string): Promise<ConfigEntry[]> {
+  const layers = [
+    { source: 'global', cfg: (await readConfigFile(configFile())) ?? {} },
+    { source: 'project', cfg: cwd ? ((await readConfigFile(configFile(cwd))) ?? {}) : {} },
+    { source: 'env', cfg: envConfig() },
+  ] as const;
+  const entries: ConfigEntry[] = [];
+  for (const key of Object.keys(CONFIG_KEYS) as ConfigKey[]) {
+    const set = layers
+      .map(({ source, cfg }) => ({ key, value: getConfigValue(cfg, key), source }))
+      .filter((entry) => entry.value !== undefined);
+    const winner = set.at(-1);
+    for (const entry of set) {
+      entries.push({ ...entry, effective: APPENDING_KEYS.has(key) || entry === winner });
+    }
+  }
+  return entries;
+}
+
+/** The effective value of `key` (for `cwd`'s project, when given): a list key joins
+ *  both layers, any other key is project over global. Undefined when unset. */
+export async function effectiveConfigValue(key: ConfigKey, cwd?: string): Promise<unknown> {
+  const values = (await configEntries(cwd))
+    .filter((entry) => entry.key === key && entry.effective)
+    .map((entry) => entry.value);
+  if (APPENDING_KEYS.has(key)) return values.length ? values.flat() : undefined;
+  return values.at(-1);
 }
@@ -0,0 +1,35 @@
+import { expect, test } from 'bun:test';
+
+import { isLocalEngine, pingEngine, summarizePings } from './engine-client.ts';
+
+test('ping stats are min/avg/max over the round-trips that came back', () => {
+  expect(summarizePings(3, [2, 4, 9])).toEqual({ sent: 3, received: 3, min: 2, avg: 5, max: 9 });
+  expect(summarizePings(2, [])).toEqual({ sent: 2, received: 0 });
+});
+
+test('ping counts only successful health checks', async () => {
+  let calls = 0;
+  const server = Bun.serve({
+    port: 0,
+    fetch: () => {
+      calls += 1;
+      // Every other check fails, as a flaky engine would.
+      return calls % 2 ? Response.json({ ok: true }) : new Response('down', { status: 503 });
+    },
+  });
+  try {
+    const summary = await pingEngine(4, 1000, `http://localhost:${server.port}`);
+    expect(summary.sent).toBe(4);
+    expect(summary.received).toBe(2);
+    expect(summary.min).toBeGreaterThanOrEqual(0);
+  } finally {
+    server.stop(true);
+  }
+});
+
+test('an engine is local when it is on a loopback host', () => {
+  expect(isLocalEngine('http://localhost:4517')).toBe(true);
+  expect(isLocalEngine('http://127.0.0.1:4517')).toBe(true);
+  expect(isLocalEngine('http://[::1]:4517')).toBe(true);
+  expect(isLocalEngine('http://devbox.lan:4517')).toBe(false);
+});
@@ -114,8 +114,59 @@ export interface SessionSummary {
   model?: string;
   worktree?: string;
   cwd?: string;
+  lastActivity?: number;
 }
 
 export async function listSessions(): Promise<SessionSummary[]> {
   return engineFetch('/api/sessions');
 }
+
+/** The environment kild injected into a live session's worker. */
+export async function getSessionEnv(id: string): Promise<Record<string, string>> {
+  return engineFetch(`/api/sessions/${encodeURIComponent(id)}/env`);
+}
+
+/** Round-trip stats for `kild ping`, in milliseconds; undefined when nothing came back. */
+export interface PingSummary {
+  sent: number;
+  received: number;
+  min?: number;
+  avg?: number;
+  max?: number;
+}
+
+export function summarizePings(sent: number, rtts: number[]): PingSummary {
+  if (rtts.length === 0) return { sent, received: 0 };
+  const total = rtts.reduce((sum, rtt) => sum + rtt, 0);
+  return {
+    sent,
+    received: rtts.length,
+    min: Math.min(...rtts),
+    avg: total / rtts.length,
+    max: Math.max(...rtts),
+  };
+}
+
+/** Is the engine at `url` on this machine? */
+export function isLocalEngine(url = ENGINE): boolean {
+  const host = new URL(url).hostname;
+  return host === 'localhost' || host === '[::1]' || host.startsWith('127.');
+}
+
+/** Time `count` sequential health checks against the engine. One that errors or
+ *  outlasts `timeoutMs` is lost, not fatal — the summary's received count shows it. */
+export async function pingEngine(
+  count: number,
+  timeoutMs = 2000,
+  engine = ENGINE,
+): Promise<PingSummary> {
+  const rtts: number[] = [];
+  for (let i = 0; i < count; i++) {
+    const start = performance.now();
+    const ok = await fetch(`${engine}/api/health`, { signal: AbortSignal.timeout(timeoutMs) })
+      .then((r) => r.ok)
+      .catch(() => false);
+    if (ok) rtts.push(performance.now() - start);
+  }
+  return summarizePings(count, rtts);
+}
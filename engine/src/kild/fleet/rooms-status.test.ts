@@ -1,6 +1,6 @@
 import { expect, test } from 'bun:test';
 
-import { compactLiveRooms, formatCompactGitSummary } from './rooms-status.ts';
+import { compactLiveRooms, formatCompactGitSummary, formatPorcelainRoom } from './rooms-status.ts';
 
 test('formatCompactGitSummary returns empty for absent status', () => {
   expect(formatCompactGitSummary()).toEqual('');
@@ -54,6 +54,35 @@ test('formatCompactGitSummary appends dirty and conflict markers', () => {
   ).toEqual(' · feature-x +2/-0 dirty CONFLICTS');
 });
 
+test('porcelain is one tab-separated line with stable 0/1 flags', () => {
+  expect(
+    formatPorcelainRoom({
+      id: 'r1',
+      name: 'auth',
+      participants: [],
+      posts: [],
+      git: {
+        path: '/tmp/ws',
+        branch: 'kild/auth',
+        base: 'main',
+        ahead: 3,
+        behind: 1,
+        dirty: true,
+        uncommittedFiles: 2,
+        changedFileCount: 5,
+        conflictsWithBase: false,
+      },
+      collidesWith: [{ room: 'billing', files: ['src/a.ts'] }],
+    }),
+  ).toEqual('r1\tauth\tkild/auth\t3\t1\t1\t0\t5\t1');
+});
+
+test('porcelain marks unknown git values with -', () => {
+  expect(formatPorcelainRoom({ id: 'r2', name: 'idle', participants: [], posts: [] })).toEqual(
+    'r2\tidle\t-\t-\t-\t-\t-\t-\t0',
+  );
+});
+
 test('a live room with no log compacts to an empty post list', () => {
   expect(
     compactLiveRooms([
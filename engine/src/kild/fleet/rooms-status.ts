@@ -22,6 +22,7 @@ export interface CompactGitStatus {
   uncommittedFiles: number;
   changedFileCount: number;
   conflictsWithBase: boolean | null;
+  stashCount?: number;
   error?: string;
 }
 
@@ -31,6 +32,27 @@ export function formatCompactGitSummary(git?: CompactGitStatus): string {
   return ` · ${git.branch ?? '?'} +${git.ahead}/-${git.behind}${git.dirty ? ' dirty' : ''}${git.conflictsWithBase ? ' CONFLICTS' : ''}`;
 }
 
+/** `kild rooms --porcelain`: one tab-separated line per room, stable for scripts —
+ *  `id name branch ahead behind dirty conflicts changed collisions`. Flags are `0`/`1`;
+ *  an unknown value (no git status, unnamed branch, conflict check not run) is `-`. */
+export function formatPorcelainRoom(room: CompactRoomStatus): string {
+  const git = room.git;
+  const flag = (value: boolean | null | undefined) =>
+    value === null || value === undefined ? '-' : value ? '1' : '0';
+  const num = (value: number | undefined) => (value === undefined ? '-' : String(value));
+  return [
+    room.id,
+    room.name,
+    git?.branch ?? '-',
+    num(git?.ahead),
+    num(git?.behind),
+    flag(git?.dirty),
+    flag(git?.conflictsWithBase),
+    num(git?.changedFileCount),
+    String(room.collidesWith?.length ?? 0),
+  ].join('\t');
+}
+
 /** One overlap: `room` also changed `files`. The specific overlapping files ARE the
  *  actionable signal (which is why they're surfaced compactly, unlike the full list). */
 export interface WorkstreamCollision {
@@ -0,0 +1,191 @@
+import { afterEach, beforeEach, expect, test } from 'bun:test';
+import { execFile as execFileCb } from 'node:child_process';
+import { mkdtempSync, rmSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+
+import {
+  CI_CACHE_TTL_MS,
+  type CiState,
+  findCiStatusCached,
+  findPrCached,
+  forgeCacheHit,
+  forgeFromRemoteUrl,
+  PR_CACHE_TTL_MS,
+  type PrInfo,
+  parseCiChecks,
+  parsePrView,
+  prCreateCommand,
+  recordPr,
+  remoteHost,
+} from './forge.ts';
+
+const execFile = promisify(execFileCb);
+
+let repo: string;
+let home: string;
+let prevHome: string | undefined;
+
+const git = (...args: string[]) => execFile('git', ['-C', repo, ...args]);
+const commit = (msg: string) => git('commit', '-q', '--allow-empty', '-m', msg);
+
+beforeEach(async () => {
+  repo = mkdtempSync(path.join(tmpdir(), 'kild-forge-repo-'));
+  home = mkdtempSync(path.join(tmpdir(), 'kild-forge-home-'));
+  prevHome = process.env.KILD_HOME;
+  process.env.KILD_HOME = home; // the forge caches live under $KILD_HOME/cache
+  await git('init', '-q', '-b', 'main');
+  await git('config', 'user.email', 't@t');
+  await git('config', 'user.name', 't');
+  await commit('init');
+});
+
+afterEach(() => {
+  if (prevHome === undefined) delete process.env.KILD_HOME;
+  else process.env.KILD_HOME = prevHome;
+  rmSync(repo, { recursive: true, force: true });
+  rmSync(home, { recursive: true, force: true });
+});
+
+const req = { branch: 'kild/fix', base: 'dev', title: 'Fix it', body: 'Body' };
+
+test('GitHub PRs are opened with gh pr create', () => {
+  expect(prCreateCommand('github', { ...req, draft: true })).toEqual({
+    cmd: 'gh',
+    args: [
+      'pr',
+      'create',
+      '--head',
+      'kild/fix',
+      '--base',
+      'dev',
+      '--title',
+      'Fix it',
+      '--body',
+      'Body',
+      '--draft',
+    ],
+  });
+});
+
+test('GitLab merge requests are opened with glab mr create, never prompting', () => {
+  expect(prCreateCommand('gitlab', req)).toEqual({
+    cmd: 'glab',
+    args: [
+      'mr',
+      'create',
+      '--source-branch',
+      'kild/fix',
+      '--target-branch',
+      'dev',
+      '--title',
+      'Fix it',
+      '--description',
+      'Body',
+      '--yes',
+    ],
+  });
+});
+
+test('Bitbucket has no PR CLI', () => {
+  expect(prCreateCommand('bitbucket', req)).toBeUndefined();
+});
+
+test('remote URLs map to their forge across URL forms', () => {
+  const cases: Array<[string, string | undefined]> = [
+    ['git@github.com:me/repo.git', 'github'],
+    ['https://github.com/me/repo.git', 'github'],
+    ['ssh://git@github.acme.com:2222/me/repo.git', 'github'],
+    ['https://gitlab.com/group/sub/repo.git', 'gitlab'],
+    ['git@gitlab.internal:group/repo.git', 'gitlab'],
+    ['https://me@bitbucket.org/team/repo.git', 'bitbucket'],
+    ['git@bitbucket.org:team/repo.git', 'bitbucket'],
+    ['https://git.example.com/repo.git', undefined],
+    ['/srv/git/repo.git', undefined],
+  ];
+  for (const [url, forge] of cases) expect(forgeFromRemoteUrl(url)).toBe(forge);
+  expect(remoteHost('ssh://git@Host.Example:22/x')).toBe('host.example');
+});
+
+test('PR view output parses for both CLIs', () => {
+  expect(parsePrView('github', '{"state":"MERGED","url":"https://gh/pr/1"}')).toEqual({
+    state: 'merged',
+    url: 'https://gh/pr/1',
+  });
+  expect(parsePrView('gitlab', '{"state":"opened","web_url":"https://gl/mr/2"}')).toEqual({
+    state: 'open',
+    url: 'https://gl/mr/2',
+  });
+  expect(parsePrView('github', 'not json')).toBeUndefined();
+});
+
+test('a cached PR answers only for the same branch tip and within the TTL', () => {
+  const entry = { sha: 'abc', fetchedAt: 1_000, pr: null };
+
+  expect(forgeCacheHit(entry, 'abc', 1_000 + PR_CACHE_TTL_MS - 1)).toBe(true);
+  expect(forgeCacheHit(entry, 'def', 1_001)).toBe(false);
+  expect(forgeCacheHit(entry, 'abc', 1_000 + PR_CACHE_TTL_MS)).toBe(false);
+  expect(forgeCacheHit(undefined, 'abc', 1_000)).toBe(false);
+  expect(forgeCacheHit(entry, 'abc', 1_000 + CI_CACHE_TTL_MS, CI_CACHE_TTL_MS)).toBe(false);
+});
+
+test('PR lookups are reused until the branch tip moves', async () => {
+  const open: PrInfo = { state: 'open', url: 'https://github.com/o/r/pull/1' };
+  let calls = 0;
+  const lookup = async () => {
+    calls += 1;
+    return open;
+  };
+
+  expect(await findPrCached(repo, 'github', 'main', lookup)).toEqual(open);
+  expect(await findPrCached(repo, 'github', 'main', lookup)).toEqual(open);
+  expect(calls).toBe(1);
+
+  await commit('b');
+  await findPrCached(repo, 'github', 'main', lookup);
+  expect(calls).toBe(2);
+});
+
+test('a PR kild just opened replaces a cached "no PR" without asking the forge', async () => {
+  let calls = 0;
+  const lookup = async () => {
+    calls += 1;
+    return null;
+  };
+  expect(await findPrCached(repo, 'github', 'main', lookup)).toBeNull();
+
+  const opened: PrInfo = { state: 'open', url: 'https://github.com/o/r/pull/2' };
+  await recordPr(repo, 'main', opened);
+
+  expect(await findPrCached(repo, 'github', 'main', lookup)).toEqual(opened);
+  expect(calls).toBe(1);
+});
+
+test('CI checks summarize to failing, pending, passing or none for both CLIs', () => {
+  const gh = (...buckets: string[]) => JSON.stringify(buckets.map((bucket) => ({ bucket })));
+  expect(parseCiChecks('github', gh('pass', 'fail', 'pending'))).toBe('failing');
+  expect(parseCiChecks('github', gh('pass', 'cancel'))).toBe('failing');
+  expect(parseCiChecks('github', gh('pass', 'pending'))).toBe('pending');
+  expect(parseCiChecks('github', gh('pass', 'skipping'))).toBe('passing');
+  expect(parseCiChecks('github', '[]')).toBe('none');
+  expect(parseCiChecks('gitlab', '{"status":"success"}')).toBe('passing');
+  expect(parseCiChecks('gitlab', '{"status":"failed"}')).toBe('failing');
+  expect(parseCiChecks('gitlab', '{"status":"running"}')).toBe('pending');
+  expect(parseCiChecks('github', 'not json')).toBeUndefined();
+});
+
+test('a cached CI state is reused for the same head, and a new head refetches', async () => {
+  const pr: PrInfo = { state: 'open', url: 'https://github.com/o/r/pull/1' };
+  const answers: CiState[] = ['pending', 'passing'];
+  let calls = 0;
+  const lookup = async () => answers[calls++];
+
+  expect(await findCiStatusCached(repo, 'github', 'main', pr, lookup)).toBe('pending');
+  expect(await findCiStatusCached(repo, 'github', 'main', pr, lookup)).toBe('pending');
+  expect(calls).toBe(1);
+
+  await commit('b');
+  expect(await findCiStatusCached(repo, 'github', 'main', pr, lookup)).toBe('passing');
+  expect(calls).toBe(2);
+});
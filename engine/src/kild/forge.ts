@@ -0,0 +1,374 @@
+import { execFile as execFileCb } from 'node:child_process';
+import { createHash } from 'node:crypto';
+import { mkdirSync, readFileSync, writeFileSync } from 'node:fs';
+import path from 'node:path';
+import { promisify } from 'node:util';
+
+import { kildHome } from './config.ts';
+import { branchIsFullyPushed } from './worktree-status.ts';
+
+/**
+ * The code forge a repo's `origin` lives on, and the forge CLI kild drives for it
+ * (`gh` for GitHub, `glab` for GitLab). kild never talks to a forge API itself — the
+ * user's authenticated CLI does, so kild holds no tokens. execFile (no shell): titles
+ * and bodies are agent-written text.
+ */
+const execFile = promisify(execFileCb);
+
+const errText = (err: unknown): string => (err instanceof Error ? err.message : String(err));
+
+export type Forge = 'github' | 'gitlab' | 'bitbucket';
+
+/** The host of a remote URL: `scheme://[user@]host[:port]/…` or scp-style
+ *  `[user@]host:path`. Undefined for a local path. */
+export function remoteHost(url: string): string | undefined {
+  const trimmed = url.trim();
+  const scheme = /^[a-z+]+:\/\/(?:[^@/]*@)?([^:/]+)/i.exec(trimmed);
+  if (scheme) return scheme[1]?.toLowerCase();
+  const scp = /^(?:[^@/]+@)?([^:/]+):/.exec(trimmed);
+  return scp?.[1]?.toLowerCase();
+}
+
+/** The forge a remote URL points at, judged by its host name — which also catches the
+ *  usual self-hosted names (`github.acme.com`, `gitlab.internal`). Undefined when the
+ *  host names no known forge. */
+export function forgeFromRemoteUrl(url: string): Forge | undefined {
+  const host = remoteHost(url);
+  if (!host) return undefined;
+  if (host.includes('github')) return 'github';
+  if (host.includes('gitlab')) return 'gitlab';
+  if (host.includes('bitbucket')) return 'bitbucket';
+  return undefined;
+}
+
+/** The forge of `repo`'s `origin` remote, or undefined (no origin, unknown host). */
+export async function repoForge(repo: string): Promise<Forge | undefined> {
+  const url = await execFile('git', ['-C', repo, 'remote', 'get-url', 'origin'])
+    .then((r) => r.stdout.trim())
+    .catch(() => '');
+  return forgeFromRemoteUrl(url);
+}
+
+/** Push `branch` to `origin` (setting upstream) unless every commit is already there —
+ *  a PR can only be opened for what the forge has. */
+export async function ensurePushed(repo: string, branch: string): Promise<void> {
+  if (await branchIsFullyPushed(repo, branch)) return;
+  await execFile('git', ['-C', repo, 'push', '--set-upstream', 'origin', branch]);
+}
+
+export interface PrCreateRequest {
+  /** The branch to open the PR from (already pushed). */
+  branch: string;
+  /** The branch it merges into. */
+  base: string;
+  title: string;
+  body: string;
+  draft?: boolean;
+}
+
+/** The forge CLI invocation that opens a PR (GitLab: merge request) for `req`. Pure, so
+ *  the argument mapping is testable without a forge. Undefined for a forge kild has
+ *  no CLI for (Bitbucket). */
+export function prCreateCommand(
+  forge: Forge,
+  req: PrCreateRequest,
+): { cmd: string; args: string[] } | undefined {
+  if (forge === 'bitbucket') return undefined;
+  if (forge === 'github') {
+    return {
+      cmd: 'gh',
+      args: [
+        'pr',
+        'create',
+        '--head',
+        req.branch,
+        '--base',
+        req.base,
+        '--title',
+        req.title,
+        '--body',
+        req.body,
+        ...(req.draft ? ['--draft'] : []),
+      ],
+    };
+  }
+  return {
+    cmd: 'glab',
+    args: [
+      'mr',
+      'create',
+      '--source-branch',
+      req.branch,
+      '--target-branch',
+      req.base,
+      '--title',
+      req.title,
+      '--description',
+      req.body,
+      '--yes', // never prompt — kild runs it non-interactively
+      ...(req.draft ? ['--draft'] : []),
+    ],
+  };
+}
+
+/** Why a PR wasn't opened: the forge CLI isn't installed (`no_cli`), the branch already
+ *  has an open PR (`exists`), or the CLI failed for another reason (`failed`). */
+export type PrCreateResult =
+  | { ok: true; url: string }
+  | { ok: false; code: 'no_cli' | 'exists' | 'failed'; error: string };
+
+/** Open a PR for `req` via the forge CLI, run in `repo`. Refusals are data. */
+export async function createPr(
+  repo: string,
+  forge: Forge,
+  req: PrCreateRequest,
+): Promise<PrCreateResult> {
+  const command = prCreateCommand(forge, req);
+  if (!command) return { ok: false, code: 'no_cli', error: `no supported CLI for ${forge}` };
+  const { cmd, args } = command;
+  try {
+    const { stdout } = await execFile(cmd, args, { cwd: repo });
+    // Both CLIs print the new PR's URL as the last line of output.
+    const url = stdout.trim().split('\n').reverse().find((line) => /^https?:\/\//.test(line));
+    return { ok: true, url: url ?? stdout.trim() };
+  } catch (err) {
+    if ((err as NodeJS.ErrnoException).code === 'ENOENT') {
+      return { ok: false, code: 'no_cli', error: `${cmd} is not installed (needed for ${forge})` };
+    }
+    const stderr = String((err as { stderr?: unknown }).stderr ?? '');
+    if (/already exists/i.test(stderr)) {
+      return { ok: false, code: 'exists', error: stderr.trim() || errText(err) };
+    }
+    return { ok: false, code: 'failed', error: stderr.trim() || errText(err) };
+  }
+}
+
+export type PrState = 'open' | 'merged' | 'closed';
+
+/** The PR (GitLab: MR) a branch has on its forge. */
+export interface PrInfo {
+  state: PrState;
+  url: string;
+}
+
+/** Parse `gh pr view --json state,url` (`OPEN`/`MERGED`/`CLOSED`) or `glab mr view
+ *  --output json` (`opened`/`merged`/`closed`, `web_url`). Undefined if unrecognised. */
+export function parsePrView(forge: Forge, stdout: string): PrInfo | undefined {
+  let raw: { state?: unknown; url?: unknown; web_url?: unknown };
+  try {
+    raw = JSON.parse(stdout);
+  } catch {
+    return undefined;
+  }
+  const state = String(raw.state ?? '').toLowerCase();
+  const url = forge === 'gitlab' ? raw.web_url : raw.url;
+  const mapped: PrState | undefined =
+    state === 'open' || state === 'opened'
+      ? 'open'
+      : state === 'merged' || state === 'closed'
+        ? state
+        : undefined;
+  return mapped && typeof url === 'string' ? { state: mapped, url } : undefined;
+}
+
+/** The PR for `branch` on `forge`, via its CLI run in `repo`: `null` when the branch
+ *  has none, undefined when that can't be told (no CLI, not authenticated, Bitbucket).
+ *  Never throws — a lookup must not block the caller's own work. */
+export async function findPr(
+  repo: string,
+  forge: Forge,
+  branch: string,
+): Promise<PrInfo | null | undefined> {
+  if (forge === 'bitbucket') return undefined;
+  const { cmd, args } =
+    forge === 'github'
+      ? { cmd: 'gh', args: ['pr', 'view', branch, '--json', 'state,url'] }
+      : { cmd: 'glab', args: ['mr', 'view', branch, '--output', 'json'] };
+  try {
+    const { stdout } = await execFile(cmd, args, { cwd: repo });
+    return parsePrView(forge, stdout);
+  } catch (err) {
+    const stderr = String((err as { stderr?: unknown }).stderr ?? '');
+    return /no (pull|merge) requests? found|not found/i.test(stderr) ? null : undefined;
+  }
+}
+
+/** A cached {@link findPr} answer: the branch tip it was looked up for, and when. */
+export interface PrCacheEntry {
+  sha: string;
+  fetchedAt: number;
+  pr: PrInfo | null;
+}
+
+/** How long a cached PR lookup is trusted while the branch tip is unchanged. */
+export const PR_CACHE_TTL_MS = 5 * 60_000;
+
+/** Can a cached forge answer (`entry`) stand for a branch now at `sha`? Only while
+ *  fresh (younger than `ttl`) and for the same tip — a new commit (a push, a rebase)
+ *  can mean a new PR, a changed one, or a fresh CI run. */
+export function forgeCacheHit(
+  entry: { sha: string; fetchedAt: number } | undefined,
+  sha: string,
+  now: number,
+  ttl = PR_CACHE_TTL_MS,
+): boolean {
+  return entry !== undefined && entry.sha === sha && now - entry.fetchedAt < ttl;
+}
+
+/** The cache file for `key` under `$KILD_HOME/cache/<kind>/` (hashed: keys are paths
+ *  and URLs). */
+function forgeCacheFile(kind: 'pr' | 'ci', key: string): string {
+  const hash = createHash('sha1').update(key).digest('hex');
+  return path.join(kildHome(), 'cache', kind, `${hash}.json`);
+}
+
+/** A cache entry, or undefined when there is none yet or it is corrupt (look it up again). */
+function readForgeCache<T>(file: string): T | undefined {
+  try {
+    return JSON.parse(readFileSync(file, 'utf8')) as T;
+  } catch {
+    return undefined;
+  }
+}
+
+function writeForgeCache(file: string, entry: unknown): void {
+  try {
+    mkdirSync(path.dirname(file), { recursive: true });
+    writeFileSync(file, JSON.stringify(entry));
+  } catch {
+    // best-effort: an unwritable cache only costs the next lookup
+  }
+}
+
+/** `branch`'s tip SHA in `repo`, or '' when it can't be resolved. */
+async function branchTip(repo: string, branch: string): Promise<string> {
+  return execFile('git', ['-C', repo, 'rev-parse', '--verify', '--quiet', branch])
+    .then((r) => r.stdout.trim())
+    .catch(() => '');
+}
+
+/** {@link findPr} behind a short-lived cache under `$KILD_HOME/cache/pr/`, keyed by
+ *  repo + branch and invalidated when the branch tip moves — so a status sweep over
+ *  many worktrees doesn't re-query the forge (slow, rate-limited) for each one every
+ *  time. A failed lookup (undefined) is never cached. `lookup` is injectable for tests.
+ *  Never throws. */
+export async function findPrCached(
+  repo: string,
+  forge: Forge,
+  branch: string,
+  lookup: typeof findPr = findPr,
+  now = Date.now(),
+): Promise<PrInfo | null | undefined> {
+  const sha = await branchTip(repo, branch);
+  if (!sha) return lookup(repo, forge, branch);
+  const file = forgeCacheFile('pr', `${repo}\0${branch}`);
+  const entry = readForgeCache<PrCacheEntry>(file);
+  if (entry && forgeCacheHit(entry, sha, now)) return entry.pr;
+  const pr = await lookup(repo, forge, branch);
+  if (pr !== undefined) writeForgeCache(file, { sha, fetchedAt: now, pr } satisfies PrCacheEntry);
+  return pr;
+}
+
+/** Record `pr` as `branch`'s current PR in {@link findPrCached}'s cache, at the branch's
+ *  tip — for when kild itself just opened it, so a cached "no PR" doesn't outlive the
+ *  PR by a whole TTL (opening one doesn't move the tip). Best-effort, never throws. */
+export async function recordPr(
+  repo: string,
+  branch: string,
+  pr: PrInfo,
+  now = Date.now(),
+): Promise<void> {
+  const sha = await branchTip(repo, branch);
+  if (!sha) return;
+  const entry: PrCacheEntry = { sha, fetchedAt: now, pr };
+  writeForgeCache(forgeCacheFile('pr', `${repo}\0${branch}`), entry);
+}
+
+/** A PR's CI verdict: every check passed, one failed (or was cancelled), some are still
+ *  running, or the PR has no checks at all. */
+export type CiState = 'passing' | 'failing' | 'pending' | 'none';
+
+/** Parse `gh pr checks --json bucket` (one `pass`/`fail`/`pending`/`skipping`/`cancel`
+ *  per check) or `glab ci get --output json` (the branch's latest pipeline, `status`).
+ *  Any failure fails the PR; otherwise anything unfinished keeps it pending. Undefined
+ *  if unrecognised. */
+export function parseCiChecks(forge: Forge, stdout: string): CiState | undefined {
+  let raw: unknown;
+  try {
+    raw = JSON.parse(stdout);
+  } catch {
+    return undefined;
+  }
+  if (forge === 'gitlab') {
+    const status = String((raw as { status?: unknown } | null)?.status ?? '');
+    if (!status) return undefined;
+    if (status === 'success') return 'passing';
+    if (status === 'failed' || status === 'canceled') return 'failing';
+    return status === 'skipped' ? 'none' : 'pending';
+  }
+  if (!Array.isArray(raw)) return undefined;
+  const buckets = raw.map((check) => String((check as { bucket?: unknown })?.bucket ?? ''));
+  if (buckets.length === 0) return 'none';
+  if (buckets.some((b) => b === 'fail' || b === 'cancel')) return 'failing';
+  if (buckets.some((b) => b !== 'pass' && b !== 'skipping')) return 'pending';
+  return 'passing';
+}
+
+/** The CI state of `branch`'s PR on `forge`, via its CLI run in `repo`. Undefined when
+ *  it can't be told (no CLI, not authenticated, Bitbucket). Never throws. */
+export async function findCiStatus(
+  repo: string,
+  forge: Forge,
+  branch: string,
+): Promise<CiState | undefined> {
+  if (forge === 'bitbucket') return undefined;
+  const { cmd, args } =
+    forge === 'github'
+      ? { cmd: 'gh', args: ['pr', 'checks', branch, '--json', 'bucket'] }
+      : { cmd: 'glab', args: ['ci', 'get', '--branch', branch, '--output', 'json'] };
+  try {
+    const { stdout } = await execFile(cmd, args, { cwd: repo });
+    return parseCiChecks(forge, stdout);
+  } catch (err) {
+    // `gh pr checks` exits non-zero for failing (1) and pending (8) checks, still
+    // printing them; with no checks at all it says so on stderr.
+    const { stdout, stderr } = err as { stdout?: unknown; stderr?: unknown };
+    if (/no checks reported/i.test(String(stderr ?? ''))) return 'none';
+    return parseCiChecks(forge, String(stdout ?? ''));
+  }
+}
+
+/** A cached {@link findCiStatus} answer: the branch tip it was read for, and when. */
+export interface CiCacheEntry {
+  sha: string;
+  fetchedAt: number;
+  state: CiState;
+}
+
+/** How long a cached CI state is trusted while the tip is unchanged — shorter than a
+ *  PR lookup's: checks finish without the tip moving. */
+export const CI_CACHE_TTL_MS = 60_000;
+
+/** {@link findCiStatus} for `pr` (the PR `branch` has) behind a short-lived cache under
+ *  `$KILD_HOME/cache/ci/`, keyed by the PR and invalidated when the branch tip moves —
+ *  a new head means a new CI run. A failed lookup (undefined) is never cached. `lookup`
+ *  is injectable for tests. Never throws. */
+export async function findCiStatusCached(
+  repo: string,
+  forge: Forge,
+  branch: string,
+  pr: PrInfo,
+  lookup: typeof findCiStatus = findCiStatus,
+  now = Date.now(),
+): Promise<CiState | undefined> {
+  const sha = await branchTip(repo, branch);
+  if (!sha) return lookup(repo, forge, branch);
+  const file = forgeCacheFile('ci', pr.url);
+  const entry = readForgeCache<CiCacheEntry>(file);
+  if (entry && forgeCacheHit(entry, sha, now, CI_CACHE_TTL_MS)) return entry.state;
+  const state = await lookup(repo, forge, branch);
+  if (state !== undefined) {
+    writeForgeCache(file, { sha, fetchedAt: now, state } satisfies CiCacheEntry);
+  }
+  return state;
+}
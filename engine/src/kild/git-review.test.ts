@@ -6,6 +6,8 @@ import path from 'node:path';
 import { promisify } from 'node:util';
 
 import {
+  branchDiff,
+  branchDiffStat,
   DIFF_CAP,
   parseCommitLog,
   parseNameStatusZ,
@@ -98,6 +100,77 @@ test('no commits ahead of base yields an empty list, no error', async () => {
   expect(result.commits).toEqual([]);
 });
 
+test('commits of a named branch: only its own, read from the main checkout', async () => {
+  const dir = await initRepo();
+  await git(dir, ['checkout', '-q', '-b', 'kild/feature']);
+  fs.writeFileSync(path.join(dir, 'f.txt'), 'f\n');
+  await git(dir, ['add', '.']);
+  await commit(dir, 'feature work');
+  await git(dir, ['checkout', '-q', 'main']);
+  fs.writeFileSync(path.join(dir, 'm.txt'), 'm\n');
+  await git(dir, ['add', '.']);
+  await commit(dir, 'main moved on');
+
+  const result = await reviewCommits(dir, 'main', 'kild/feature');
+  const missing = await reviewCommits(dir, 'main', 'kild/nope');
+
+  expect(result.error).toBeUndefined();
+  expect(result.commits.map((c) => c.subject)).toEqual(['feature work']);
+  expect(missing.error).toBe('ref not found: kild/nope');
+});
+
+test('branch diff stat is the net committed diff from the merge-base', async () => {
+  const dir = await initRepo();
+  await git(dir, ['checkout', '-q', '-b', 'feature']);
+  fs.writeFileSync(path.join(dir, 'a.txt'), 'one\ntwo\n');
+  await git(dir, ['add', '.']);
+  await commit(dir, 'add a');
+  fs.writeFileSync(path.join(dir, 'README.md'), 'changed\n');
+  await git(dir, ['add', '.']);
+  await commit(dir, 'edit readme');
+  fs.writeFileSync(path.join(dir, 'uncommitted.txt'), 'x\n');
+  await git(dir, ['checkout', '-q', 'main']);
+
+  const result = await branchDiffStat(dir, 'main', 'feature');
+
+  expect(result.error).toBeUndefined();
+  expect(result.stat).toEqual({ files: 2, additions: 3, deletions: 1, binaryFiles: 0 });
+});
+
+test('branch diff stat counts a binary-only change as a changed file', async () => {
+  const dir = await initRepo();
+  await git(dir, ['checkout', '-q', '-b', 'feature']);
+  fs.writeFileSync(path.join(dir, 'logo.png'), Buffer.from([0x89, 0x50, 0x00, 0x01, 0x02]));
+  await git(dir, ['add', '.']);
+  await commit(dir, 'add logo');
+
+  const result = await branchDiffStat(dir, 'main', 'feature');
+
+  expect(result.stat).toEqual({ files: 1, additions: 0, deletions: 0, binaryFiles: 1 });
+});
+
+test('branch diff is the patch from the merge-base; base-only commits are not in it', async () => {
+  const dir = await initRepo();
+  await git(dir, ['checkout', '-q', '-b', 'feature']);
+  fs.writeFileSync(path.join(dir, 'a.txt'), 'one\ntwo\n');
+  await git(dir, ['add', '.']);
+  await commit(dir, 'add a');
+  await git(dir, ['checkout', '-q', 'main']);
+  fs.writeFileSync(path.join(dir, 'main-only.txt'), 'base\n');
+  await git(dir, ['add', '.']);
+  await commit(dir, 'advance main');
+
+  const result = await branchDiff(dir, 'main', 'feature');
+  const stat = await branchDiffStat(dir, 'main', 'feature');
+
+  expect(result.error).toBeUndefined();
+  expect(result.patch).toContain('+++ b/a.txt');
+  expect(result.patch).toContain('+two');
+  expect(result.patch).not.toContain('main-only.txt');
+  expect(stat.stat).toEqual({ files: 1, additions: 2, deletions: 0, binaryFiles: 0 });
+  expect((await branchDiff(dir, 'main', 'nope')).error).toBe('ref not found: nope');
+});
+
 test('commits: a missing base ref is an error object, not a crash', async () => {
   const dir = await initRepo();
   const result = await reviewCommits(dir, 'does-not-exist');
@@ -270,9 +343,9 @@ test('parseCommitLog handles binary numstat entries and empty commits', () => {
 test('parseNumstatZ decodes plain, binary, and rename entries', () => {
   const stdout = '3\t1\tplain.ts\0-\t-\tbin.png\x005\t0\t\0old.ts\0new.ts\0';
   expect(parseNumstatZ(stdout)).toEqual([
-    { path: 'plain.ts', additions: 3, deletions: 1 },
-    { path: 'bin.png', additions: 0, deletions: 0 },
-    { path: 'new.ts', additions: 5, deletions: 0 },
+    { path: 'plain.ts', additions: 3, deletions: 1, binary: false },
+    { path: 'bin.png', additions: 0, deletions: 0, binary: true },
+    { path: 'new.ts', additions: 5, deletions: 0, binary: false },
   ]);
 });
 
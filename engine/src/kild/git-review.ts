@@ -52,6 +52,29 @@ export interface ReviewFile {
   uncommitted: boolean;
   /** For `renamed`: the pre-rename path. */
   renamedFrom?: string;
+  /** Set for a binary file — its 0/0 line counts mean "not countable", not "unchanged". */
+  binary?: true;
+}
+
+/** Net diff size of a branch vs base — what a PR of it would show. */
+export interface BranchDiffStat {
+  files: number;
+  additions: number;
+  deletions: number;
+  /** How many of `files` are binary (no line counts). */
+  binaryFiles: number;
+}
+
+export interface BranchDiffStatResult {
+  base: string;
+  stat: BranchDiffStat;
+  error?: string; // any git failure captured here, NEVER thrown
+}
+
+export interface BranchDiffResult {
+  base: string;
+  patch: string;
+  error?: string; // any git failure captured here, NEVER thrown
 }
 
 export interface ReviewCommitsResult {
@@ -89,11 +112,15 @@ async function runGit(dir: string, args: string[]): Promise<GitResult> {
   }
 }
 
-/** HEAD and the base ref must both resolve before any comparison is meaningful.
- *  Returns the error string (worktree-status wording), or undefined when fine. */
-async function verifyRepoAndBase(dir: string, base: string): Promise<string | undefined> {
-  const head = await runGit(dir, ['rev-parse', '--verify', '--quiet', 'HEAD^{commit}']);
-  if (!head.ok) return head.error; // not a git repo, or no commits yet
+/** HEAD (or `head`) and the base ref must both resolve before any comparison is
+ *  meaningful. Returns the error string (worktree-status wording), or undefined. */
+async function verifyRepoAndBase(
+  dir: string,
+  base: string,
+  head = 'HEAD',
+): Promise<string | undefined> {
+  const tip = await runGit(dir, ['rev-parse', '--verify', '--quiet', `${head}^{commit}`]);
+  if (!tip.ok) return head === 'HEAD' ? tip.error : `ref not found: ${head}`; // no repo/commits
   const baseExists = await runGit(dir, ['rev-parse', '--verify', '--quiet', `${base}^{commit}`]);
   if (!baseExists.ok) return `base ref not found: ${base}`;
   return undefined;
@@ -139,14 +166,16 @@ export function parseCommitLog(stdout: string): ReviewCommit[] {
 
 /** Parse `git diff --numstat -z` output. Plain entry: `adds\tdels\tpath NUL`;
  *  rename entry: `adds\tdels\t NUL oldpath NUL newpath NUL` (keyed by the new path).
- *  Binary files report `-\t-` — counted as 0/0. */
+ *  Binary files report `-\t-` — counted as 0/0 and flagged `binary`. */
 export function parseNumstatZ(stdout: string): Array<{
   path: string;
   additions: number;
   deletions: number;
+  binary: boolean;
 }> {
   const tokens = stdout.split('\0');
-  const entries: Array<{ path: string; additions: number; deletions: number }> = [];
+  const entries: Array<{ path: string; additions: number; deletions: number; binary: boolean }> =
+    [];
   for (let i = 0; i < tokens.length; i++) {
     const token = tokens[i];
     if (!token) continue;
@@ -160,7 +189,8 @@ export function parseNumstatZ(stdout: string): Array<{
       i += 2;
       filePath = tokens[i] ?? '';
     }
-    if (filePath) entries.push({ path: filePath, additions, deletions });
+    const binary = stat[1] === '-' && stat[2] === '-';
+    if (filePath) entries.push({ path: filePath, additions, deletions, binary });
   }
   return entries;
 }
@@ -220,11 +250,17 @@ export function parsePorcelainZ(stdout: string): {
 // ── Probes ────────────────────────────────────────────────────────────────────
 
 /** Commits on the workstream branch that base doesn't have (`base..HEAD`), newest
- *  first, each with its own diff stats. Never throws — failures land in `error`. */
-export async function reviewCommits(dir: string, base?: string): Promise<ReviewCommitsResult> {
+ *  first, each with its own diff stats. `head` names another branch to inspect (e.g.
+ *  `kild/<name>` from the main checkout, whether or not its tree exists). Never
+ *  throws — failures land in `error`. */
+export async function reviewCommits(
+  dir: string,
+  base?: string,
+  head = 'HEAD',
+): Promise<ReviewCommitsResult> {
   const resolvedBase = base ?? (await resolveDefaultBase(dir));
   const result: ReviewCommitsResult = { base: resolvedBase, commits: [] };
-  const invalid = await verifyRepoAndBase(dir, resolvedBase);
+  const invalid = await verifyRepoAndBase(dir, resolvedBase, head);
   if (invalid) {
     result.error = invalid;
     return result;
@@ -233,7 +269,7 @@ export async function reviewCommits(dir: string, base?: string): Promise<ReviewC
     'log',
     '--numstat',
     `--format=${LOG_FORMAT}`,
-    `${resolvedBase}..HEAD`,
+    `${resolvedBase}..${head}`,
   ]);
   if (!log.ok) {
     result.error = log.error;
@@ -243,6 +279,63 @@ export async function reviewCommits(dir: string, base?: string): Promise<ReviewC
   return result;
 }
 
+/** Net committed diff of `head` vs base (`base...head`: from the merge-base, so the
+ *  base's own advances don't count) — the files/+/- a PR of the branch would show.
+ *  Uncommitted work is excluded; binary files count as changed with 0/0 lines (and in
+ *  `binaryFiles`). Never throws. */
+export async function branchDiffStat(
+  dir: string,
+  base?: string,
+  head = 'HEAD',
+): Promise<BranchDiffStatResult> {
+  const resolvedBase = base ?? (await resolveDefaultBase(dir));
+  const result: BranchDiffStatResult = {
+    base: resolvedBase,
+    stat: { files: 0, additions: 0, deletions: 0, binaryFiles: 0 },
+  };
+  const invalid = await verifyRepoAndBase(dir, resolvedBase, head);
+  if (invalid) {
+    result.error = invalid;
+    return result;
+  }
+  const numstat = await runGit(dir, ['diff', '--numstat', '-z', '-M', `${resolvedBase}...${head}`]);
+  if (!numstat.ok) {
+    result.error = numstat.error;
+    return result;
+  }
+  for (const entry of parseNumstatZ(numstat.stdout)) {
+    result.stat.files += 1;
+    result.stat.additions += entry.additions;
+    result.stat.deletions += entry.deletions;
+    if (entry.binary) result.stat.binaryFiles += 1;
+  }
+  return result;
+}
+
+/** The full committed patch of `head` vs base — the same `base...head` range as
+ *  {@link branchDiffStat}, i.e. the change a PR of the branch would contain. Uncapped
+ *  (it is for a terminal or pager, not the cockpit). Never throws. */
+export async function branchDiff(
+  dir: string,
+  base?: string,
+  head = 'HEAD',
+): Promise<BranchDiffResult> {
+  const resolvedBase = base ?? (await resolveDefaultBase(dir));
+  const result: BranchDiffResult = { base: resolvedBase, patch: '' };
+  const invalid = await verifyRepoAndBase(dir, resolvedBase, head);
+  if (invalid) {
+    result.error = invalid;
+    return result;
+  }
+  const patch = await runGit(dir, ['diff', '-M', `${resolvedBase}...${head}`]);
+  if (!patch.ok) {
+    result.error = patch.error;
+    return result;
+  }
+  result.patch = patch.stdout;
+  return result;
+}
+
 /** The merge-base of base and HEAD — the same baseline `base...HEAD` uses, computed
  *  explicitly so the working tree can be diffed against it directly (covering
  *  committed + uncommitted in one diff) without the base's own advances ever reading
@@ -314,6 +407,7 @@ export async function reviewFiles(dir: string, base?: string): Promise<ReviewFil
       status: entry.status,
       uncommitted: uncommitted.has(entry.path),
       ...(entry.renamedFrom ? { renamedFrom: entry.renamedFrom } : {}),
+      ...(stat?.binary ? { binary: true as const } : {}),
     });
   }
 
@@ -0,0 +1,24 @@
+import { expect, test } from 'bun:test';
+
+import { matchesAnyGlob } from './glob.ts';
+
+test('a slash-free pattern matches the basename at any depth', () => {
+  expect(matchesAnyGlob('notes.local.md', ['*.local.md'])).toBe(true);
+  expect(matchesAnyGlob('docs/deep/notes.local.md', ['*.local.md'])).toBe(true);
+  expect(matchesAnyGlob('src/main.ts', ['*.local.md'])).toBe(false);
+});
+
+test('a pattern with a slash matches the whole path; ** spans directories', () => {
+  expect(matchesAnyGlob('.env/dev', ['.env/*'])).toBe(true);
+  expect(matchesAnyGlob('sub/.env/dev', ['.env/*'])).toBe(false);
+  expect(matchesAnyGlob('a/b/c/scratch.txt', ['a/**/scratch.txt'])).toBe(true);
+  expect(matchesAnyGlob('scratch.txt', ['**/scratch.txt'])).toBe(true);
+  expect(matchesAnyGlob('tmp/x.log', ['/tmp/?.log'])).toBe(true);
+});
+
+test('regex metacharacters in a pattern are literal', () => {
+  expect(matchesAnyGlob('a+b.txt', ['a+b.txt'])).toBe(true);
+  expect(matchesAnyGlob('aab.txt', ['a+b.txt'])).toBe(false);
+  expect(matchesAnyGlob('axtxt', ['a?txt'])).toBe(true);
+  expect(matchesAnyGlob('a.txt', ['a?txt'])).toBe(true);
+});
@@ -0,0 +1,33 @@
+/**
+ * Minimal path globs for config-supplied patterns (e.g. `cleanIgnoreGlobs`): `*` and
+ * `?` match within one path segment, `**` across segments. Like a `.gitignore` line, a
+ * pattern without `/` matches a file's basename at any depth; one with `/` matches the
+ * whole repo-relative path. No braces or classes — config stays readable.
+ */
+function globToRegExp(pattern: string): RegExp {
+  let source = '';
+  for (let i = 0; i < pattern.length; i++) {
+    const ch = pattern[i] as string;
+    if (ch === '*' && pattern[i + 1] === '*') {
+      // `**/` also matches zero directories, so `**/x` matches a top-level `x`.
+      const slash = pattern[i + 2] === '/';
+      source += slash ? '(?:.*/)?' : '.*';
+      i += slash ? 2 : 1;
+    } else if (ch === '*') {
+      source += '[^/]*';
+    } else if (ch === '?') {
+      source += '[^/]';
+    } else {
+      source += ch.replace(/[.+^${}()|[\]\\]/g, '\\$&');
+    }
+  }
+  return new RegExp(`^${source}$`);
+}
+
+/** Does repo-relative `file` match any of `patterns`? */
+export function matchesAnyGlob(file: string, patterns: string[]): boolean {
+  return patterns.some((pattern) => {
+    const subject = pattern.includes('/') ? file : (file.split('/').pop() ?? file);
+    return globToRegExp(pattern.replace(/^\//, '')).test(subject);
+  });
+}
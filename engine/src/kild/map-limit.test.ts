@@ -0,0 +1,35 @@
+import { expect, test } from 'bun:test';
+
+import { mapLimit } from './map-limit.ts';
+
+const tick = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+test('results match a sequential map, in input order, whatever finishes first', async () => {
+  const items = [5, 1, 4, 2, 3, 0];
+  const fn = async (n: number) => {
+    await tick(n);
+    return `r${n}`;
+  };
+  const sequential: string[] = [];
+  for (const n of items) sequential.push(await fn(n));
+
+  expect(await mapLimit(items, 3, fn)).toEqual(sequential);
+});
+
+test('never more than `limit` calls are in flight', async () => {
+  let inFlight = 0;
+  let peak = 0;
+
+  await mapLimit([...Array(10).keys()], 3, async () => {
+    inFlight += 1;
+    peak = Math.max(peak, inFlight);
+    await tick(1);
+    inFlight -= 1;
+  });
+
+  expect(peak).toBe(3);
+});
+
+test('an empty input resolves to an empty list', async () => {
+  expect(await mapLimit([], 4, async () => 1)).toEqual([]);
+});
@@ -0,0 +1,24 @@
+/**
+ * `Promise.all` over `items` with at most `limit` calls of `fn` in flight — for fan-outs
+ * that spawn subprocesses (a git status per live room), where an unbounded
+ * `Promise.all` spawns one batch of git processes per item at once. Results keep the
+ * input order, so the output is the same as a sequential loop's. Rejects on the first
+ * rejection, like `Promise.all`.
+ */
+export async function mapLimit<T, R>(
+  items: readonly T[],
+  limit: number,
+  fn: (item: T) => Promise<R>,
+): Promise<R[]> {
+  const results = new Array<R>(items.length);
+  let next = 0;
+  const worker = async () => {
+    while (next < items.length) {
+      const i = next++;
+      results[i] = await fn(items[i] as T);
+    }
+  };
+  const workers = Array.from({ length: Math.max(1, Math.min(limit, items.length)) }, worker);
+  await Promise.all(workers);
+  return results;
+}
@@ -0,0 +1,17 @@
+import { expect, test } from 'bun:test';
+
+import { isWslKernel, openCommand } from './opener.ts';
+
+test('WSL kernels are recognized from /proc/version', () => {
+  expect(isWslKernel('Linux version 5.15.153.1-microsoft-standard-WSL2 (gcc …)')).toBe(true);
+  expect(isWslKernel('Linux version 4.4.0-19041-Microsoft (Microsoft@Microsoft.com)')).toBe(true);
+  expect(isWslKernel('Linux version 6.8.0-45-generic (buildd@lcy02-amd64-075)')).toBe(false);
+});
+
+test('the opener is open on macOS, explorer.exe under WSL, xdg-open on other Linux', () => {
+  const url = 'https://example.com/pull/1';
+  expect(openCommand(url, 'darwin', false)).toEqual({ cmd: 'open', args: [url] });
+  expect(openCommand(url, 'linux', true)).toEqual({ cmd: 'explorer.exe', args: [url] });
+  expect(openCommand(url, 'linux', false)).toEqual({ cmd: 'xdg-open', args: [url] });
+  expect(openCommand('C:\\wt', 'linux', true).args).toEqual(['C:\\wt']);
+});
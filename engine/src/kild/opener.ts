@@ -0,0 +1,54 @@
+import { execFile as execFileCb } from 'node:child_process';
+import { readFileSync } from 'node:fs';
+import { promisify } from 'node:util';
+
+// execFile (no shell): targets are validated by the caller but still never parsed by one.
+const execFile = promisify(execFileCb);
+
+export interface OpenCommand {
+  cmd: string;
+  args: string[];
+}
+
+/** Is `procVersion` (the contents of `/proc/version`) a WSL kernel? Microsoft builds
+ *  name themselves there (`…-microsoft-standard-WSL2`, `…-Microsoft` on WSL1). */
+export function isWslKernel(procVersion: string): boolean {
+  return /microsoft/i.test(procVersion);
+}
+
+/** Is the engine running under WSL? Never throws — unreadable means no. */
+export function isWsl(): boolean {
+  if (process.platform !== 'linux') return false;
+  try {
+    return isWslKernel(readFileSync('/proc/version', 'utf8'));
+  } catch {
+    return false;
+  }
+}
+
+/** The command that hands `target` (a URL, or a path already in the host's form) to the
+ *  OS default handler. Under WSL that is Windows' `explorer.exe` — a Linux desktop
+ *  opener (`xdg-open`) is usually absent there, and the browser and file manager the
+ *  user sees are Windows ones. Pure: the platform and WSL check are passed in. */
+export function openCommand(target: string, platform: string, wsl: boolean): OpenCommand {
+  if (platform === 'darwin') return { cmd: 'open', args: [target] };
+  if (wsl) return { cmd: 'explorer.exe', args: [target] };
+  return { cmd: 'xdg-open', args: [target] };
+}
+
+/** Open a URL or a local path with the OS default handler (see {@link openCommand}).
+ *  Under WSL a path is first translated to its Windows form (`wslpath -w`). Throws when
+ *  the opener can't be run. */
+export async function openExternal(target: string, kind: 'url' | 'path'): Promise<void> {
+  const wsl = isWsl();
+  const hostTarget =
+    wsl && kind === 'path' ? (await execFile('wslpath', ['-w', target])).stdout.trim() : target;
+  const { cmd, args } = openCommand(hostTarget, process.platform, wsl);
+  try {
+    await execFile(cmd, args);
+  } catch (err) {
+    // explorer.exe exits 1 even when it opened the target; only a failure to run it
+    // (no exit code, e.g. ENOENT) is a real error there.
+    if (!(wsl && typeof (err as { code?: unknown }).code === 'number')) throw err;
+  }
+}
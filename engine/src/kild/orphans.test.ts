@@ -0,0 +1,217 @@
+import { afterEach, beforeEach, expect, test } from 'bun:test';
+import { execFile as execFileCb } from 'node:child_process';
+import { mkdtempSync, rmSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+
+import {
+  approveOrphans,
+  type CleanupDecision,
+  type Orphan,
+  parseCleanupDecision,
+  removeOrphan,
+  scanForOrphans,
+  scanForOrphansAllProjects,
+  untrackedWorktrees,
+} from './orphans.ts';
+import { addProject } from './projects.ts';
+import { ensureWorktree, removeWorktree } from './worktree.ts';
+
+const execFile = promisify(execFileCb);
+
+let home: string;
+let prevHome: string | undefined;
+const dirs: string[] = [];
+
+const gitIn = (dir: string, ...args: string[]) => execFile('git', ['-C', dir, ...args]);
+
+async function initRepo(): Promise<string> {
+  const repo = mkdtempSync(path.join(tmpdir(), 'kild-orphan-repo-'));
+  dirs.push(repo);
+  await gitIn(repo, 'init', '-q', '-b', 'main');
+  await gitIn(repo, 'config', 'user.email', 't@t');
+  await gitIn(repo, 'config', 'user.name', 't');
+  await gitIn(repo, 'commit', '-q', '--allow-empty', '-m', 'init');
+  return repo;
+}
+
+// A kild branch whose tree was removed with `kild worktree rm` (the branch is kept).
+async function orphanBranch(repo: string, name: string, commit = false): Promise<void> {
+  const wt = await ensureWorktree(repo, name);
+  if (commit) await gitIn(wt.path, 'commit', '-q', '--allow-empty', '-m', 'work');
+  await removeWorktree(repo, wt.path);
+}
+
+beforeEach(() => {
+  home = mkdtempSync(path.join(tmpdir(), 'kild-orphan-home-'));
+  prevHome = process.env.KILD_HOME;
+  process.env.KILD_HOME = home; // worktrees + projects.json live under $KILD_HOME
+});
+
+afterEach(() => {
+  if (prevHome === undefined) delete process.env.KILD_HOME;
+  else process.env.KILD_HOME = prevHome;
+  for (const dir of dirs.splice(0)) rmSync(dir, { recursive: true, force: true });
+  rmSync(home, { recursive: true, force: true });
+});
+
+test('a kild branch whose worktree was removed is an orphan; a live worktree is not', async () => {
+  const repo = await initRepo();
+  await orphanBranch(repo, 'gone');
+  await ensureWorktree(repo, 'live');
+
+  const orphans = await scanForOrphans(repo);
+
+  expect(orphans).toHaveLength(1);
+  expect(orphans[0]).toMatchObject({ kind: 'branch', name: 'gone', branch: 'kild/gone', repo });
+});
+
+test('untracked worktrees are the non-kild trees under the worktree root', () => {
+  const root = '/home/u/.kild/worktrees';
+  const trees = [
+    { branch: 'main', path: '/src/repo' },
+    { branch: 'kild/ours', path: `${root}/ours`, name: 'ours' },
+    { branch: 'feature', path: `${root}/by-hand` },
+    { branch: '(detached)', path: `${root}/detached` },
+    { branch: 'elsewhere', path: '/tmp/elsewhere' },
+    { branch: 'sibling', path: `${root}-other/x` },
+  ];
+
+  expect(untrackedWorktrees(trees, root).map((t) => t.branch)).toEqual(['feature', '(detached)']);
+  // A detached tree at a kild branch's dir is kild's own, not a hand-made one.
+  expect(untrackedWorktrees(trees, root, ['detached']).map((t) => t.branch)).toEqual([
+    'feature',
+  ]);
+});
+
+test('a hand-made worktree under the root is reported as untracked', async () => {
+  const repo = await initRepo();
+  await gitIn(repo, 'worktree', 'add', '-q', '-b', 'feature', path.join(home, 'worktrees', 'x'));
+
+  const orphans = await scanForOrphans(repo);
+
+  expect(orphans).toHaveLength(1);
+  expect(orphans[0]).toMatchObject({
+    kind: 'untracked_worktree',
+    name: 'feature',
+    branch: 'feature',
+  });
+});
+
+test('a worktree dir deleted outside git is a stale entry, and removal prunes it', async () => {
+  const repo = await initRepo();
+  const wt = await ensureWorktree(repo, 'deleted');
+  rmSync(wt.path, { recursive: true, force: true });
+
+  const orphans = await scanForOrphans(repo);
+  expect(orphans).toHaveLength(1);
+  expect(orphans[0]).toMatchObject({
+    kind: 'stale_worktree',
+    name: 'deleted',
+    branch: 'kild/deleted',
+  });
+
+  const stale = orphans[0];
+  if (!stale) throw new Error('expected a stale worktree');
+  await expect(removeOrphan(stale)).resolves.toEqual({ ok: true });
+  // Pruned: the branch is no longer checked out anywhere, so it is now the orphan.
+  expect((await scanForOrphans(repo)).map((o) => o.kind)).toEqual(['branch']);
+});
+
+test('removing one stale entry leaves the other stale entries registered', async () => {
+  const repo = await initRepo();
+  for (const name of ['one', 'two']) {
+    rmSync((await ensureWorktree(repo, name)).path, { recursive: true, force: true });
+  }
+  const one = (await scanForOrphans(repo)).find((o) => o.name === 'one');
+  if (!one) throw new Error('expected a stale worktree');
+
+  await expect(removeOrphan(one)).resolves.toEqual({ ok: true });
+
+  const left = await scanForOrphans(repo);
+  expect(left.map((o) => `${o.kind} ${o.name}`).sort()).toEqual([
+    'branch one',
+    'stale_worktree two',
+  ]);
+});
+
+test('an unmerged orphan branch is kept unless forced', async () => {
+  const repo = await initRepo();
+  await orphanBranch(repo, 'wip', true);
+  const [orphan] = await scanForOrphans(repo);
+  if (!orphan) throw new Error('expected an orphan');
+
+  const kept = await removeOrphan(orphan);
+  expect(kept.ok).toBe(false);
+  expect((await gitIn(repo, 'branch')).stdout).toContain('kild/wip');
+
+  await expect(removeOrphan(orphan, true)).resolves.toEqual({ ok: true });
+  expect((await gitIn(repo, 'branch')).stdout).not.toContain('kild/wip');
+});
+
+test('the cross-project scan aggregates orphans across projects, tagged by project', async () => {
+  const alpha = await initRepo();
+  const beta = await initRepo();
+  await orphanBranch(alpha, 'alpha-gone');
+  await orphanBranch(beta, 'beta-gone');
+  await addProject('alpha', alpha);
+  await addProject('beta', beta);
+
+  const { orphans, skipped } = await scanForOrphansAllProjects();
+
+  expect(skipped).toEqual([]);
+  expect(orphans.map((o) => [o.project, o.branch]).sort()).toEqual([
+    ['alpha', 'kild/alpha-gone'],
+    ['beta', 'kild/beta-gone'],
+  ]);
+});
+
+test('the cross-project scan skips a project whose path no longer exists', async () => {
+  const alpha = await initRepo();
+  const moved = await initRepo();
+  await orphanBranch(alpha, 'alpha-gone');
+  await addProject('alpha', alpha);
+  await addProject('moved', moved);
+  rmSync(moved, { recursive: true, force: true });
+
+  const { orphans, skipped } = await scanForOrphansAllProjects();
+
+  expect(orphans.map((o) => o.project)).toEqual(['alpha']);
+  expect(skipped).toEqual([{ project: 'moved', path: moved, reason: 'path no longer exists' }]);
+});
+
+const fakeOrphan = (name: string): Orphan => ({
+  kind: 'branch',
+  repo: '/r',
+  name,
+  branch: `kild/${name}`,
+  path: `/w/${name}`,
+});
+
+test('the interactive approval loop honours yes/no, then all approves the rest', async () => {
+  const orphans = ['a', 'b', 'c', 'd', 'e'].map(fakeOrphan);
+  const answers: CleanupDecision[] = ['yes', 'no', 'all'];
+  const asked: string[] = [];
+
+  const approved = await approveOrphans(orphans, async (o) => {
+    asked.push(o.name);
+    return answers.shift() ?? 'quit';
+  });
+
+  expect(asked).toEqual(['a', 'b', 'c']);
+  expect(approved.map((o) => o.name)).toEqual(['a', 'c', 'd', 'e']);
+});
+
+test('quit stops the approval loop; typed answers parse, unknown ones do not', async () => {
+  const answers = ['y', 'q'].map((a) => parseCleanupDecision(a) ?? 'no');
+
+  const approved = await approveOrphans(['a', 'b'].map(fakeOrphan), async () => {
+    return answers.shift() ?? 'no';
+  });
+
+  expect(approved.map((o) => o.name)).toEqual(['a']);
+  expect(parseCleanupDecision('')).toBe('no');
+  expect(parseCleanupDecision(' ALL ')).toBe('all');
+  expect(parseCleanupDecision('maybe')).toBeUndefined();
+});
@@ -0,0 +1,238 @@
+import { execFile as execFileCb } from 'node:child_process';
+import { existsSync } from 'node:fs';
+import path from 'node:path';
+import { promisify } from 'node:util';
+
+import { loadProjects } from './projects.ts';
+import {
+  isGitRepo,
+  kildBranchNames,
+  listWorktrees,
+  removeWorktree,
+  resolvedWorktreesRoot,
+  type Worktree,
+  worktreeNameForDir,
+  worktreePath,
+  worktreeRef,
+} from './worktree.ts';
+import { branchIsFullyPushed } from './worktree-status.ts';
+
+const execFile = promisify(execFileCb);
+
+const errText = (err: unknown): string => (err instanceof Error ? err.message : String(err));
+
+/**
+ * Orphaned kild resources — what a worktree's lifecycle can strand. `kild worktree rm`
+ * frees the tree but deliberately keeps the `kild/<name>` branch, and a tree deleted
+ * behind git's back leaves a stale admin entry. Neither shows in `worktree ls`, so this
+ * scan is how they're found (and, via {@link removeOrphan}, cleaned up).
+ *
+ * - `branch` — a local `kild/*` branch no worktree has checked out.
+ * - `stale_worktree` — a `kild/*` worktree git still registers whose dir is gone.
+ * - `untracked_worktree` — a worktree made by hand under kild's worktree root, on a
+ *   non-`kild/*` branch, so kild never lists it. Adopt it (`adoptWorktree`) or remove it.
+ */
+export type OrphanKind = 'branch' | 'stale_worktree' | 'untracked_worktree';
+
+export interface Orphan {
+  kind: OrphanKind;
+  /** The repo the resource belongs to. */
+  repo: string;
+  /** Registered project name — set by the cross-project scan only. */
+  project?: string;
+  /** kild worktree name (the branch minus `kild/`; for an untracked worktree, its
+   *  branch — the name it would be adopted under). */
+  name: string;
+  branch: string;
+  /** Where the worktree is (`stale_worktree`, `untracked_worktree`) or would be (`branch`). */
+  path: string;
+}
+
+/** A registered project the cross-project scan could not inspect. */
+export interface ProjectScanSkip {
+  project: string;
+  path: string;
+  reason: string;
+}
+
+export interface CrossProjectScan {
+  orphans: Orphan[];
+  skipped: ProjectScanSkip[];
+}
+
+export type OrphanRemoveResult = { ok: true } | { ok: false; error: string };
+
+/** The worktrees under `root` that kild doesn't track — i.e. not on a `kild/*` branch.
+ *  A detached tree whose dir names one of `kildNames` (see {@link worktreeNameForDir})
+ *  is kild's own tree with its branch detached, so it isn't untracked either. Pure set
+ *  difference over `git worktree list`; `root` and the tree paths must be spelled alike
+ *  (the scan resolves both through realpath). */
+export function untrackedWorktrees(
+  trees: Worktree[],
+  root: string,
+  kildNames: string[] = [],
+): Worktree[] {
+  return trees.filter((tree) => {
+    if (tree.name) return false;
+    if (tree.branch === '(detached)' && worktreeNameForDir(tree.path, root, kildNames)) {
+      return false;
+    }
+    const rel = path.relative(root, tree.path);
+    return rel !== '' && !rel.startsWith('..') && !path.isAbsolute(rel);
+  });
+}
+
+/** Orphaned kild resources in one repo. Throws on a git failure (e.g. not a repo). */
+export async function scanForOrphans(repo: string): Promise<Orphan[]> {
+  const trees = await listWorktrees(repo);
+  const orphans: Orphan[] = [];
+  for (const tree of trees) {
+    if (!tree.name || existsSync(tree.path)) continue;
+    orphans.push({
+      kind: 'stale_worktree',
+      repo,
+      name: tree.name,
+      branch: tree.branch,
+      path: tree.path,
+    });
+  }
+
+  const kildNames = await kildBranchNames(repo);
+  for (const tree of untrackedWorktrees(trees, resolvedWorktreesRoot(repo), kildNames)) {
+    orphans.push({
+      kind: 'untracked_worktree',
+      repo,
+      name: tree.branch,
+      branch: tree.branch,
+      path: tree.path,
+    });
+  }
+
+  // A stale entry still counts as checking its branch out (git won't delete it until
+  // the entry is pruned), so it is excluded here as well.
+  const checkedOut = new Set(trees.map((tree) => tree.branch));
+  for (const name of kildNames) {
+    let branch: string;
+    let wtPath: string;
+    try {
+      branch = worktreeRef(name);
+      wtPath = worktreePath(name, repo);
+    } catch {
+      continue; // a name outside kild's allowlist was never created by kild — not ours
+    }
+    if (checkedOut.has(branch)) continue;
+    orphans.push({ kind: 'branch', repo, name, branch, path: wtPath });
+  }
+  return orphans;
+}
+
+/** {@link scanForOrphans} over every registered project, each orphan tagged with its
+ *  project. A project whose path is gone (or isn't a usable repo) is skipped and
+ *  reported in `skipped` — one moved repo must not hide every other project's orphans. */
+export async function scanForOrphansAllProjects(): Promise<CrossProjectScan> {
+  const result: CrossProjectScan = { orphans: [], skipped: [] };
+  for (const project of await loadProjects()) {
+    if (!existsSync(project.path)) {
+      result.skipped.push({
+        project: project.name,
+        path: project.path,
+        reason: 'path no longer exists',
+      });
+      continue;
+    }
+    if (!(await isGitRepo(project.path))) {
+      result.skipped.push({
+        project: project.name,
+        path: project.path,
+        reason: 'not a git repository',
+      });
+      continue;
+    }
+    try {
+      for (const orphan of await scanForOrphans(project.path)) {
+        result.orphans.push({ ...orphan, project: project.name });
+      }
+    } catch (err) {
+      result.skipped.push({ project: project.name, path: project.path, reason: errText(err) });
+    }
+  }
+  return result;
+}
+
+/** Remove one orphan. A stale entry's registration is dropped (its dir is already gone —
+ *  nothing to lose) with `git worktree remove` on its path, which touches that entry
+ *  alone — unlike `git worktree prune`, which sweeps every stale entry. A branch is
+ *  `-d`-deleted, so git refuses an unmerged one and the refusal is returned as data;
+ *  only `force` (`-D`) discards unmerged commits. An untracked worktree goes through the
+ *  ordinary clean-only removal — `force` never applies to a tree kild didn't create. */
+export async function removeOrphan(orphan: Orphan, force = false): Promise<OrphanRemoveResult> {
+  try {
+    if (orphan.kind === 'untracked_worktree') {
+      const result = await removeWorktree(orphan.repo, orphan.path);
+      if (!result.ok) {
+        const files = result.files?.length ? `: ${result.files.join(', ')}` : '';
+        return { ok: false, error: `worktree ${result.code}${files}` };
+      }
+    } else if (orphan.kind === 'stale_worktree') {
+      await execFile('git', ['-C', orphan.repo, 'worktree', 'remove', orphan.path]);
+    } else {
+      await execFile('git', ['-C', orphan.repo, 'branch', force ? '-D' : '-d', orphan.branch]);
+    }
+    return { ok: true };
+  } catch (err) {
+    return { ok: false, error: errText(err) };
+  }
+}
+
+/** What removing an orphan would cost, shown before removing it: for an untracked
+ *  worktree its disk usage (KiB, `du -sk`) and dirty state; for a branch whether every
+ *  commit is on a remote (if not, a forced delete loses them). Best-effort: a field is
+ *  undefined if its probe fails or doesn't apply. */
+export async function orphanDetails(
+  orphan: Orphan,
+): Promise<{ sizeKb?: number; dirty?: boolean; pushed?: boolean }> {
+  if (orphan.kind === 'branch') {
+    return { pushed: await branchIsFullyPushed(orphan.repo, orphan.branch) };
+  }
+  if (orphan.kind !== 'untracked_worktree') return {};
+  const [du, status] = await Promise.all([
+    execFile('du', ['-sk', orphan.path]).catch(() => undefined),
+    execFile('git', ['-C', orphan.path, 'status', '--porcelain']).catch(() => undefined),
+  ]);
+  const sizeKb = du ? Number.parseInt(du.stdout, 10) : Number.NaN;
+  return {
+    sizeKb: Number.isNaN(sizeKb) ? undefined : sizeKb,
+    dirty: status ? status.stdout.trim() !== '' : undefined,
+  };
+}
+
+/** An answer to "remove this orphan?" in `kild worktree cleanup --interactive`. */
+export type CleanupDecision = 'yes' | 'no' | 'all' | 'quit';
+
+/** Parse a typed answer (`y`/`n`/`a`/`q` or the full word; empty means no). Undefined
+ *  for anything else, so the caller re-asks rather than guessing. */
+export function parseCleanupDecision(answer: string): CleanupDecision | undefined {
+  const a = answer.trim().toLowerCase();
+  if (a === 'y' || a === 'yes') return 'yes';
+  if (a === '' || a === 'n' || a === 'no') return 'no';
+  if (a === 'a' || a === 'all') return 'all';
+  if (a === 'q' || a === 'quit') return 'quit';
+  return undefined;
+}
+
+/** The orphans approved for removal, asking `ask` about each in turn: `all` approves
+ *  this one and the rest without asking again, `quit` stops (nothing after it is
+ *  removed). `ask` is injected so the loop is testable without a TTY. */
+export async function approveOrphans(
+  orphans: Orphan[],
+  ask: (orphan: Orphan) => Promise<CleanupDecision>,
+): Promise<Orphan[]> {
+  const approved: Orphan[] = [];
+  for (const [i, orphan] of orphans.entries()) {
+    const decision = await ask(orphan);
+    if (decision === 'quit') break;
+    if (decision === 'all') return [...approved, ...orphans.slice(i)];
+    if (decision === 'yes') approved.push(orphan);
+  }
+  return approved;
+}
@@ -0,0 +1,60 @@
+import { expect, test } from 'bun:test';
+
+import type { ReviewCommit } from './git-review.ts';
+import { generatePrBody, generatePrTitle } from './pr-body.ts';
+
+const commit = (sha: string, subject: string): ReviewCommit => ({
+  sha,
+  subject,
+  author: 't',
+  ts: 0,
+  filesChanged: 1,
+  additions: 1,
+  deletions: 0,
+});
+
+test('the body lists every commit subject oldest-first and the diff summary', () => {
+  const body = generatePrBody(
+    [commit('bbbbbbbbbb', 'Add tests'), commit('aaaaaaaaaa', 'Fix the parser')],
+    { files: 3, additions: 40, deletions: 7, binaryFiles: 0 },
+  );
+
+  expect(body).toBe(
+    [
+      '## Changes',
+      '',
+      '- Fix the parser (aaaaaaa)',
+      '- Add tests (bbbbbbb)',
+      '',
+      '## Diff',
+      '',
+      '3 files changed, +40 -7',
+      '',
+    ].join('\n'),
+  );
+});
+
+test('a branch with nothing ahead says so', () => {
+  const body = generatePrBody([], { files: 1, additions: 0, deletions: 0, binaryFiles: 0 });
+
+  expect(body).toContain('_No commits ahead of base._');
+  expect(body).toContain('1 file changed, +0 -0');
+});
+
+test('the diff summary notes binary files, whose lines are not counted', () => {
+  const body = generatePrBody([commit('a', 'Add logo')], {
+    files: 2,
+    additions: 3,
+    deletions: 0,
+    binaryFiles: 1,
+  });
+
+  expect(body).toContain('2 files changed (1 binary), +3 -0');
+});
+
+test('the title is a lone commit subject, else the readable worktree name', () => {
+  expect(generatePrTitle('fix-auth', [commit('a', 'Fix token refresh')])).toBe('Fix token refresh');
+  expect(generatePrTitle('fix-auth_bug/v2', [commit('a', 'x'), commit('b', 'y')])).toBe(
+    'Fix auth bug v2',
+  );
+});
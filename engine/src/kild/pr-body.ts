@@ -0,0 +1,28 @@
+import type { BranchDiffStat, ReviewCommit } from './git-review.ts';
+
+/**
+ * A PR description drafted from a branch's own commits and net diff size — the
+ * starting point for `kild worktree pr-body`, so opening a PR for an agent's branch
+ * doesn't begin from a blank box. Pure: the caller gathers the git data.
+ */
+export function generatePrBody(commits: ReviewCommit[], stat: BranchDiffStat): string {
+  const lines = ['## Changes', ''];
+  if (commits.length === 0) lines.push('_No commits ahead of base._');
+  // Review probes list newest first; a changelog reads oldest first.
+  for (const commit of [...commits].reverse()) {
+    lines.push(`- ${commit.subject} (${commit.sha.slice(0, 7)})`);
+  }
+  const binary = stat.binaryFiles ? ` (${stat.binaryFiles} binary)` : '';
+  const files = `${stat.files} file${stat.files === 1 ? '' : 's'} changed${binary}`;
+  lines.push('', '## Diff', '', `${files}, +${stat.additions} -${stat.deletions}`);
+  return `${lines.join('\n')}\n`;
+}
+
+/** A PR title: the subject of a single-commit branch, else the worktree name made
+ *  readable (`fix-auth-bug` → `Fix auth bug`). `--title` overrides it. */
+export function generatePrTitle(name: string, commits: ReviewCommit[]): string {
+  const [only] = commits;
+  if (only && commits.length === 1) return only.subject;
+  const words = name.replace(/[-_/]+/g, ' ').trim();
+  return words.charAt(0).toUpperCase() + words.slice(1);
+}
@@ -0,0 +1,34 @@
+import { expect, test } from 'bun:test';
+
+import { prPruneVerdict } from './pr-prune.ts';
+
+const merged = { state: 'merged' as const, url: 'https://github.com/o/r/pull/1' };
+
+test('a merged PR on a clean, fully pushed tree can be pruned', () => {
+  expect(prPruneVerdict({ pr: merged, dirty: false, pushed: true })).toEqual({ ok: true });
+});
+
+test('a merged PR is still kept while the tree is dirty or has unpushed commits', () => {
+  expect(prPruneVerdict({ pr: merged, dirty: true, pushed: true })).toEqual({
+    ok: false,
+    reason: 'uncommitted changes',
+  });
+  expect(prPruneVerdict({ pr: merged, dirty: false, pushed: false })).toEqual({
+    ok: false,
+    reason: 'unpushed commits',
+  });
+});
+
+test('an open, closed, missing, or unknown PR is never pruned', () => {
+  const clean = { dirty: false, pushed: true };
+  const open = { state: 'open' as const, url: 'u' };
+  const closed = { state: 'closed' as const, url: 'u' };
+
+  expect(prPruneVerdict({ pr: open, ...clean })).toEqual({ ok: false, reason: 'PR is open' });
+  expect(prPruneVerdict({ pr: closed, ...clean })).toEqual({ ok: false, reason: 'PR is closed' });
+  expect(prPruneVerdict({ pr: null, ...clean })).toEqual({ ok: false, reason: 'no PR' });
+  expect(prPruneVerdict({ pr: undefined, ...clean })).toEqual({
+    ok: false,
+    reason: 'PR lookup failed',
+  });
+});
@@ -0,0 +1,99 @@
+import { execFile as execFileCb } from 'node:child_process';
+import { promisify } from 'node:util';
+
+import { configuredDirtyPolicy } from './config.ts';
+import { findPrCached, type PrInfo, repoForge } from './forge.ts';
+import { listWorktrees, removeWorktree } from './worktree.ts';
+import { branchIsFullyPushed, workstreamGitStatus } from './worktree-status.ts';
+
+const execFile = promisify(execFileCb);
+
+/** Whether a worktree can be pruned for its merged PR, and if not, why it is kept. */
+export type PrPruneVerdict = { ok: true } | { ok: false; reason: string };
+
+export interface PrPruneResult {
+  pruned: string[];
+  skipped: Array<{ name: string; reason: string }>;
+  /** Candidates a git step failed on partway (e.g. the tree was removed but the branch
+   *  delete failed) — the rest of the run carries on. */
+  failed: Array<{ name: string; error: string }>;
+}
+
+/**
+ * Can a worktree be pruned because its PR merged? `pruneMergedWorktrees` only catches
+ * branches git sees as merged; a squash- or rebase-merged PR leaves the branch
+ * "unmerged" forever, so the forge's word is used instead. Pure: `pr` is the lookup
+ * result (`null` no PR, `undefined` lookup failed). Only a merged PR on a clean tree
+ * whose every commit is on the remote qualifies — anything else could lose work.
+ */
+export function prPruneVerdict(input: {
+  pr: PrInfo | null | undefined;
+  dirty: boolean;
+  pushed: boolean;
+}): PrPruneVerdict {
+  if (input.pr === undefined) return { ok: false, reason: 'PR lookup failed' };
+  if (input.pr === null) return { ok: false, reason: 'no PR' };
+  if (input.pr.state !== 'merged') return { ok: false, reason: `PR is ${input.pr.state}` };
+  if (input.dirty) return { ok: false, reason: 'uncommitted changes' };
+  if (!input.pushed) return { ok: false, reason: 'unpushed commits' };
+  return { ok: true };
+}
+
+/** For each `kild/*` worktree of `repo` whose PR merged (per {@link prPruneVerdict}),
+ *  remove the tree and delete its branch. `keep` names worktrees a live session is
+ *  using — never pruned. `only`, when given, limits the run to those names (the ones a
+ *  user confirmed after a dry run); other trees are left out of the result. Dirty means
+ *  what it means to `removeWorktree`: the repo's configured dirty policy applies. With
+ *  `dryRun`, nothing is removed: `pruned` lists what would be. Throws if the origin
+ *  remote is not on a known forge; a failure on one candidate is recorded in `failed`
+ *  and the others still run. */
+export async function pruneMergedPrWorktrees(
+  repo: string,
+  keep: Set<string> = new Set(),
+  dryRun = false,
+  only?: string[],
+): Promise<PrPruneResult> {
+  const forge = await repoForge(repo);
+  if (!forge) throw new Error(`origin remote of ${repo} is not on a known forge`);
+  const result: PrPruneResult = { pruned: [], skipped: [], failed: [] };
+  const policy = await configuredDirtyPolicy(repo);
+  for (const tree of await listWorktrees(repo)) {
+    if (!tree.name || (only && !only.includes(tree.name))) continue;
+    if (keep.has(tree.name)) {
+      result.skipped.push({ name: tree.name, reason: 'in use by a live session' });
+      continue;
+    }
+    // Only the dirty verdict is read from the status, so HEAD stands in for the base:
+    // it always resolves, and any error left means the tree itself couldn't be read.
+    const [pr, status, pushed] = await Promise.all([
+      findPrCached(repo, forge, tree.branch),
+      workstreamGitStatus(tree.path, 'HEAD', policy),
+      branchIsFullyPushed(repo, tree.branch),
+    ]);
+    // An unreadable tree counts as dirty — never remove what couldn't be inspected.
+    const dirty = status.error !== undefined || status.dirty;
+    const verdict = prPruneVerdict({ pr, dirty, pushed });
+    if (!verdict.ok) {
+      result.skipped.push({ name: tree.name, reason: verdict.reason });
+      continue;
+    }
+    if (!dryRun) {
+      try {
+        const removed = await removeWorktree(repo, tree.path);
+        if (!removed.ok) {
+          result.skipped.push({ name: tree.name, reason: `worktree ${removed.code}` });
+          continue;
+        }
+        // `-D`: a squash-merged branch never reads as merged, but every commit is on the
+        // remote (checked above), so nothing is lost.
+        await execFile('git', ['-C', repo, 'branch', '-D', tree.branch]);
+      } catch (err) {
+        const error = err instanceof Error ? err.message : String(err);
+        result.failed.push({ name: tree.name, error });
+        continue;
+      }
+    }
+    result.pruned.push(tree.name);
+  }
+  return result;
+}
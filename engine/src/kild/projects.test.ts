@@ -0,0 +1,89 @@
+import { afterEach, beforeEach, expect, test } from 'bun:test';
+import { execFile as execFileCb } from 'node:child_process';
+import fs from 'node:fs';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+
+import {
+  addProject,
+  deriveProjectName,
+  loadProjects,
+  projectHealth,
+  projectNameFromRemote,
+  removeProject,
+} from './projects.ts';
+
+const execFile = promisify(execFileCb);
+
+let tmp: string;
+let prevHome: string | undefined;
+
+beforeEach(() => {
+  prevHome = process.env.KILD_HOME;
+  tmp = fs.mkdtempSync(path.join(os.tmpdir(), 'kild-projects-'));
+  process.env.KILD_HOME = path.join(tmp, 'home');
+});
+
+afterEach(() => {
+  if (prevHome === undefined) delete process.env.KILD_HOME;
+  else process.env.KILD_HOME = prevHome;
+  fs.rmSync(tmp, { recursive: true, force: true });
+});
+
+const homeFiles = () => fs.readdirSync(process.env.KILD_HOME as string);
+
+test('saving leaves valid JSON and no temp file behind', async () => {
+  await addProject('alpha', tmp);
+  await addProject('beta', tmp);
+  await removeProject('alpha');
+
+  expect(homeFiles()).toEqual(['projects.json']);
+  const raw = fs.readFileSync(path.join(process.env.KILD_HOME as string, 'projects.json'), 'utf8');
+  expect(JSON.parse(raw)).toEqual({ projects: [{ name: 'beta', path: tmp }] });
+});
+
+test('a temp file left by an interrupted save is overwritten', async () => {
+  fs.mkdirSync(process.env.KILD_HOME as string, { recursive: true });
+  fs.writeFileSync(path.join(process.env.KILD_HOME as string, 'projects.json.tmp'), '{"proj');
+
+  await addProject('alpha', tmp);
+
+  expect(homeFiles()).toEqual(['projects.json']);
+  expect(await loadProjects()).toEqual([{ name: 'alpha', path: tmp }]);
+});
+
+test('the project name is the last path segment of any remote URL form', () => {
+  const cases: Array<[string, string | undefined]> = [
+    ['git@github.com:user/repo.git', 'repo'],
+    ['https://github.com/user/repo.git', 'repo'],
+    ['https://gitlab.com/group/subgroup/tool.git', 'tool'],
+    ['git@gitlab.com:group/subgroup/tool.git', 'tool'],
+    ['https://bitbucket.org/team/my-repo', 'my-repo'],
+    ['ssh://git@git.example.com:2222/team/svc.git', 'svc'],
+    ['https://example.com/', undefined],
+  ];
+  for (const [url, name] of cases) expect(projectNameFromRemote(url)).toBe(name);
+});
+
+test('without an origin remote the name falls back to the directory name', async () => {
+  const dir = path.join(tmp, 'My Project');
+  fs.mkdirSync(dir);
+
+  expect(await deriveProjectName(dir)).toBe('My-Project');
+});
+
+test('project health tells a repo from a missing path and a plain directory', async () => {
+  const repo = path.join(tmp, 'repo');
+  const plain = path.join(tmp, 'plain');
+  fs.mkdirSync(repo);
+  fs.mkdirSync(plain);
+  await execFile('git', ['-C', repo, 'init', '-q']);
+
+  expect(await projectHealth({ name: 'repo', path: repo })).toBe('ok');
+  expect(await projectHealth({ name: 'gone', path: path.join(tmp, 'gone') })).toBe('missing');
+  expect(await projectHealth({ name: 'plain', path: plain })).toBe('not_a_repo');
+  // A plain dir inside some other checkout is not a repo of its own.
+  fs.mkdirSync(path.join(repo, 'notes'));
+  expect(await projectHealth({ name: 'notes', path: path.join(repo, 'notes') })).toBe('not_a_repo');
+});
@@ -1,7 +1,10 @@
+import { execFile as execFileCb } from 'node:child_process';
 import fs from 'node:fs/promises';
 import path from 'node:path';
+import { promisify } from 'node:util';
 
 import { kildHome } from './config.ts';
+import { isGitRepo } from './worktree.ts';
 
 /** A project is a directory an agent works in — mirror of kild-core::project. */
 export interface Project {
@@ -9,6 +12,8 @@ export interface Project {
   path: string;
 }
 
+const execFile = promisify(execFileCb);
+
 function projectsFile(): string {
   return path.join(kildHome(), 'projects.json');
 }
@@ -23,31 +28,101 @@ export async function loadProjects(): Promise<Project[]> {
   }
 }
 
+/** A project name safe as a path segment: anything outside `[A-Za-z0-9._-]` → `-`. */
+function sanitizeName(name: string): string {
+  return name.replace(/[^A-Za-z0-9._-]+/g, '-').replace(/^-+|-+$/g, '');
+}
+
+/** The repo name a remote URL ends in, `.git` stripped: `https://host/a/b/repo.git`,
+ *  scp-style `git@host:group/sub/repo.git`, `ssh://git@host:2222/repo`, `/srv/repo.git`.
+ *  Undefined when the URL has no path to name it by. */
+export function projectNameFromRemote(url: string): string | undefined {
+  const trimmed = url.trim().replace(/\/+$/, '');
+  // URL form (`scheme://host/path`), scp form (`[user@]host:path`), or a local path.
+  const pathPart = /^[a-z+]+:\/\//i.test(trimmed)
+    ? trimmed.replace(/^[a-z+]+:\/\/[^/]*/i, '')
+    : trimmed.slice(trimmed.indexOf(':') + 1);
+  const last = pathPart.split('/').pop() ?? '';
+  const name = sanitizeName(last.replace(/\.git$/, ''));
+  return name || undefined;
+}
+
+/** A default name for a project at `dir`: its `origin` remote's repo name, else the
+ *  directory name — so `kild project add <path>` needs no name for the common case. */
+export async function deriveProjectName(dir: string): Promise<string> {
+  const url = await execFile('git', ['-C', dir, 'remote', 'get-url', 'origin'])
+    .then((r) => r.stdout)
+    .catch(() => '');
+  const fromDir = sanitizeName(path.basename(path.resolve(dir)));
+  return projectNameFromRemote(url) ?? (fromDir || 'project');
+}
+
+/** Write the project list atomically: a temp file in the same dir is written and
+ *  fsynced, then renamed over `projects.json` (and the dir fsynced), so a crash
+ *  mid-write leaves the old list intact — never a truncated file `loadProjects` would
+ *  reject, losing every project. A leftover temp from an earlier crash is overwritten. */
+async function saveProjects(projects: Project[]): Promise<void> {
+  const target = projectsFile();
+  const temp = `${target}.tmp`;
+  try {
+    await fs.mkdir(kildHome(), { recursive: true });
+    const file = await fs.open(temp, 'w');
+    try {
+      await file.writeFile(JSON.stringify({ projects }, null, 2));
+      await file.sync();
+    } finally {
+      await file.close();
+    }
+    await fs.rename(temp, target);
+  } catch (err) {
+    await fs.rm(temp, { force: true }).catch(() => {});
+    throw new Error(
+      `kild: failed to save projects: ${err instanceof Error ? err.message : String(err)}`,
+    );
+  }
+  // Persist the rename itself. Directories can't be opened for fsync on Windows.
+  if (process.platform !== 'win32') {
+    const dir = await fs.open(kildHome(), 'r');
+    await dir.sync().finally(() => dir.close());
+  }
+}
+
 export async function findProject(name: string): Promise<Project | null> {
   return (await loadProjects()).find((p) => p.name === name) ?? null;
 }
 
+/** Whether a registered project is still usable. `missing`: its path is gone (the repo
+ *  moved); `not_a_repo`: the path exists but isn't a git repository. */
+export type ProjectHealth = 'ok' | 'missing' | 'not_a_repo';
+
+/** Check a project's path without changing anything. Never throws. */
+export async function projectHealth(project: Project): Promise<ProjectHealth> {
+  const stat = await fs.stat(project.path).catch(() => null);
+  if (!stat?.isDirectory()) return 'missing';
+  return (await isGitRepo(project.path)) ? 'ok' : 'not_a_repo';
+}
+
 /** Remove a project by name (no-op if absent). */
 export async function removeProject(name: string): Promise<void> {
   const projects = (await loadProjects()).filter((p) => p.name !== name);
-  await fs.mkdir(kildHome(), { recursive: true });
-  await fs.writeFile(projectsFile(), JSON.stringify({ projects }, null, 2));
+  await saveProjects(projects);
 }
 
-/** Register a project. Path must be an existing dir; names unique; `~/` expands. */
-export async function addProject(name: string, dir: string): Promise<Project> {
+/** Register a project. Path must be an existing dir; names unique; `~/` expands. An
+ *  omitted name is derived ({@link deriveProjectName}). */
+export async function addProject(name: string | undefined, dir: string): Promise<Project> {
   const resolved = dir.startsWith('~/')
     ? path.join(process.env.HOME ?? '', dir.slice(2))
     : path.resolve(dir);
   const stat = await fs.stat(resolved).catch(() => null);
   if (!stat?.isDirectory()) throw new Error(`not a directory: ${resolved}`);
+  name ??= await deriveProjectName(resolved);
 
   const projects = await loadProjects();
   if (projects.some((p) => p.name === name)) throw new Error(`duplicate project name: ${name}`);
 
   const project: Project = { name, path: resolved };
   projects.push(project);
-  await fs.mkdir(kildHome(), { recursive: true });
-  await fs.writeFile(projectsFile(), JSON.stringify({ projects }, null, 2));
+  await saveProjects(projects);
   return project;
 }
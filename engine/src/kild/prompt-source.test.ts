@@ -0,0 +1,37 @@
+import { afterEach, beforeEach, expect, test } from 'bun:test';
+import { mkdtempSync, rmSync, writeFileSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import { join } from 'node:path';
+import { Readable } from 'node:stream';
+
+import { readPromptSource } from './prompt-source.ts';
+
+let dir: string;
+
+beforeEach(() => {
+  dir = mkdtempSync(join(tmpdir(), 'kild-prompt-'));
+});
+
+afterEach(() => {
+  rmSync(dir, { recursive: true, force: true });
+});
+
+test('a prompt file is read and trimmed', async () => {
+  const file = join(dir, 'task.md');
+  writeFileSync(file, '\nFix the auth bug.\n\nThen run the tests.\n');
+  expect(await readPromptSource(file)).toBe('Fix the auth bug.\n\nThen run the tests.');
+});
+
+test('- reads the prompt from stdin, whole across chunks', async () => {
+  const euro = Buffer.from('Price in €.\n');
+  const stdin = Readable.from([euro.subarray(0, 10), euro.subarray(10)]);
+  expect(await readPromptSource('-', stdin)).toBe('Price in €.');
+});
+
+test('a blank or missing prompt file is an error', async () => {
+  const file = join(dir, 'blank.md');
+  writeFileSync(file, '  \n');
+  await expect(readPromptSource(file)).rejects.toThrow(`empty prompt: ${file}`);
+  await expect(readPromptSource(join(dir, 'nope.md'))).rejects.toThrow();
+  await expect(readPromptSource('-', Readable.from([]))).rejects.toThrow('empty prompt: stdin');
+});
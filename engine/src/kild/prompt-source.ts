@@ -0,0 +1,20 @@
+import { readFile } from 'node:fs/promises';
+
+/** Read a prompt from `source` — a file path, or `-` for all of `stdin` — for
+ *  `kild run --prompt`. Throws when the file can't be read or the prompt is blank. */
+export async function readPromptSource(
+  source: string,
+  stdin: AsyncIterable<Buffer | string> = process.stdin,
+): Promise<string> {
+  let text: string;
+  if (source === '-') {
+    const chunks: Buffer[] = [];
+    for await (const chunk of stdin) chunks.push(Buffer.from(chunk));
+    text = Buffer.concat(chunks).toString('utf8');
+  } else {
+    text = await readFile(source, 'utf8');
+  }
+  text = text.trim();
+  if (!text) throw new Error(`empty prompt: ${source === '-' ? 'stdin' : source}`);
+  return text;
+}
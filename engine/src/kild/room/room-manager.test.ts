@@ -700,7 +700,7 @@ test('workstreamDir resolves a live room without a worktree to its cwd', async (
   const { manager } = fixture();
   await openRoom(manager, [{ name: 'worker' }]);
   // base falls all the way through to 'main' (tmp is no git checkout, no config).
-  expect(manager.workstreamDir('room-1')).toEqual({
+  expect(await manager.workstreamDir('room-1')).toEqual({
     ok: true,
     value: { dir: tmp, base: 'main' },
   });
@@ -715,7 +715,7 @@ test('workstreamDir resolves a worktree room to the worktree path, with its base
     worktree: 'slice-x',
     base: 'develop',
   });
-  const result = manager.workstreamDir('room-1');
+  const result = await manager.workstreamDir('room-1');
   expect(result.ok).toBe(true);
   if (result.ok) {
     expect(result.value.dir).toBe(worktreePath('slice-x'));
@@ -723,9 +723,9 @@ test('workstreamDir resolves a worktree room to the worktree path, with its base
   }
 });
 
-test('workstreamDir on an unknown room is not_found', () => {
+test('workstreamDir on an unknown room is not_found', async () => {
   const { manager } = fixture();
-  expect(manager.workstreamDir('nope')).toEqual({
+  expect(await manager.workstreamDir('nope')).toEqual({
     ok: false,
     code: 'not_found',
     message: 'no such live room: nope',
@@ -737,7 +737,7 @@ test('workstreamDir on a closed (archived) room is invalid_state', async () => {
   await openRoom(manager, [{ name: 'worker' }]);
   await manager.postFromHuman('room-1', 'hello'); // history → the close archives it
   await manager.close('room-1');
-  expect(manager.workstreamDir('room-1')).toEqual({
+  expect(await manager.workstreamDir('room-1')).toEqual({
     ok: false,
     code: 'invalid_state',
     message: 'room room-1 is archived; its workstream is gone',
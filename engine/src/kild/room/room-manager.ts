@@ -1,10 +1,15 @@
 import { randomUUID } from 'node:crypto';
 
 import { listAgents } from '../agents.ts';
-import { configuredMemoryDir, configuredMemorySynthesis } from '../config.ts';
+import {
+  configuredDirtyPolicy,
+  configuredMemoryDir,
+  configuredMemorySynthesis,
+} from '../config.ts';
+import { mapLimit } from '../map-limit.ts';
 import { appendRoomLog, roomTranscriptPath, synthesisPrompt } from '../memory.ts';
 import { type SessionCallbacks, type SpawnRequest, sessionManager } from '../sessions.ts';
-import { resolveBaseBranch, worktreePath } from '../worktree.ts';
+import { locateWorktree, resolveBaseBranch } from '../worktree.ts';
 import { workstreamGitStatus } from '../worktree-status.ts';
 import { applyDecisionMarkers, formatOpenDecisions, openDecisions } from './room-decisions.ts';
 import {
@@ -47,6 +52,10 @@ import {
  *  just the human kill switch). */
 const MAX_PARTICIPANTS = 8;
 
+/** Rooms whose git status is probed at once by {@link RoomManager.liveRoomsStatus} —
+ *  each probe spawns several git processes, so a big fleet must not fork them all. */
+const GIT_STATUS_CONCURRENCY = 4;
+
 /** The single chokepoint every room post flows through: one structured trace line so a
  *  whole room reads back as an ordered log (grep `room.post`). Kept dead simple — swap
  *  the body for a real logger later without touching call sites. Programmatic tracers
@@ -265,24 +274,24 @@ export class RoomManager {
   /** Live rooms enriched with each workstream's git/worktree state — the code-state
    *  half of observability, so a driving agent can land work and spot collisions.
    *  Effective dir = the room's worktree if set, else its cwd. Git failures are
-   *  captured per-room (never thrown), so status stays available even mid-conflict. */
+   *  captured per-room (never thrown), so status stays available even mid-conflict.
+   *  Probes run a few rooms at a time; the result keeps registry order. */
   async liveRoomsStatus(): Promise<LiveRoomStatus[]> {
-    return Promise.all(
-      this.registry.liveRoomObjects().map(async (room) => ({
-        id: room.id,
-        name: room.name,
-        worktree: room.worktree,
-        participants: room.participants.map(participantView),
-        state: room.state,
-        log: room.log,
-        decisions: room.decisions,
-        totals: roomCostTotals(room.participants),
-        git: await workstreamGitStatus(
-          room.worktree ? worktreePath(room.worktree) : room.cwd,
-          room.base,
-        ),
-      })),
-    );
+    return mapLimit(this.registry.liveRoomObjects(), GIT_STATUS_CONCURRENCY, async (room) => ({
+      id: room.id,
+      name: room.name,
+      worktree: room.worktree,
+      participants: room.participants.map(participantView),
+      state: room.state,
+      log: room.log,
+      decisions: room.decisions,
+      totals: roomCostTotals(room.participants),
+      git: await workstreamGitStatus(
+        room.worktree ? await locateWorktree(room.cwd, room.worktree) : room.cwd,
+        room.base,
+        await configuredDirtyPolicy(room.cwd),
+      ),
+    }));
   }
 
   /** The effective workstream dir (the room's worktree if set, else its cwd) + base of
@@ -291,10 +300,11 @@ export class RoomManager {
    *  git state. Live rooms only: an archived room's participants are gone and its
    *  worktree may be pruned, so there is no workstream to inspect (`invalid_state`);
    *  an id that was never a room is `not_found`. */
-  workstreamDir(roomId: string): CommandResult<{ dir: string; base?: string }> {
+  async workstreamDir(roomId: string): Promise<CommandResult<{ dir: string; base?: string }>> {
     const room = this.registry.get(roomId);
     if (room) {
-      return ok({ dir: room.worktree ? worktreePath(room.worktree) : room.cwd, base: room.base });
+      const dir = room.worktree ? await locateWorktree(room.cwd, room.worktree) : room.cwd;
+      return ok({ dir, base: room.base });
     }
     if (this.registry.archived().some((archived) => archived.id === roomId)) {
       return fail('invalid_state', `room ${roomId} is archived; its workstream is gone`);
@@ -1,6 +1,6 @@
 import { expect, test } from 'bun:test';
 
-import { SessionManager, workerEnv } from './sessions.ts';
+import { SessionManager, sessionEnv, workerEnv } from './sessions.ts';
 import { worktreePath, worktreeRef } from './worktree.ts';
 
 // The session path derives SessionInfo.branch/worktreePath from the worktree name
@@ -57,15 +57,37 @@ test('workerEnv leaves KILD_FORK_SESSION empty for an ordinary (fresh) spawn', (
   expect(workerEnv('s-2', { cwd: '/proj' }, undefined).KILD_FORK_SESSION).toBe('');
 });
 
+test('the recorded session env carries request vars, with the KILD_* vars winning', () => {
+  const env = sessionEnv(
+    's-3',
+    { cwd: '/proj', env: { KILD_ROOM: 'r-1', KILD_SESSION_ID: 'spoofed' } },
+    undefined,
+  );
+  expect(env.KILD_ROOM).toBe('r-1');
+  expect(env.KILD_SESSION_ID).toBe('s-3');
+  expect(env.PATH).toBeUndefined(); // the inherited engine env is not recorded
+});
+
+test('env reports a live session and nothing for an unknown id', () => {
+  const sessions = new SessionManager();
+  (sessions as { sessions: Map<string, unknown> }).sessions.set('s-4', {
+    session: {},
+    info: { id: 's-4', origin: 'cli' },
+    env: { KILD_SESSION_ID: 's-4' },
+  });
+  expect(sessions.env('s-4')).toEqual({ KILD_SESSION_ID: 's-4' });
+  expect(sessions.env('missing')).toBeUndefined();
+});
+
 test('a worktree name maps to its kild/ branch and on-disk path', () => {
   const name = 'fix-auth';
   expect(worktreeRef(name)).toBe('kild/fix-auth');
   expect(worktreePath(name).replace(/\\/g, '/')).toEndWith('/worktrees/fix-auth');
 });
 
-test('a slashed worktree name keeps the slash in the ref, dashes the path', () => {
+test('a slashed worktree name keeps the slash in the ref, escapes it in the path', () => {
   expect(worktreeRef('feat/login')).toBe('kild/feat/login');
-  expect(worktreePath('feat/login').replace(/\\/g, '/')).toEndWith('/worktrees/feat-login');
+  expect(worktreePath('feat/login').replace(/\\/g, '/')).toEndWith('/worktrees/feat%2Flogin');
 });
 
 test('an unsafe worktree name throws before any I/O (spawn surfaces it as an error)', () => {
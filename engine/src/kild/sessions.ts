@@ -1,4 +1,5 @@
 import { type ChildProcess, spawn } from 'node:child_process';
+import { StringDecoder } from 'node:string_decoder';
 
 import type { UiEvent } from './events.ts';
 import type {
@@ -10,7 +11,7 @@ import type {
   RoomCommandAck,
 } from './room/room-types.ts';
 import { readSkillsProfile, skillsProfileForWorker } from './skills-profile.ts';
-import { worktreePath, worktreeRef } from './worktree.ts';
+import { locateWorktree, worktreePath, worktreeRef } from './worktree.ts';
 
 export interface SpawnRequest {
   model?: string;
@@ -47,13 +48,17 @@ export interface SessionInfo {
   worktree?: string;
   /** `kild/<name>` ref, when the session runs in a worktree (else undefined). */
   branch?: string;
-  /** Deterministic on-disk worktree path, when the session runs in a worktree. */
+  /** On-disk worktree path, when the session runs in a worktree: derived at spawn,
+   *  then corrected to where git has an existing tree. */
   worktreePath?: string;
   /** The underlying pi session id — reopen this agent in a terminal with
    *  `pi --session <piSessionFile ?? piSessionId>`. */
   piSessionId?: string;
   /** Absolute pi session file path (the robust resume handle; works from any cwd). */
   piSessionFile?: string;
+  /** Epoch millis of the worker's latest event — how stale the agent is. Unset until
+   *  the first event. */
+  lastActivity?: number;
 }
 
 /** A message broadcast to every connected client. */
@@ -92,6 +97,17 @@ export function workerEnv(
   };
 }
 
+/** Everything the engine injects into a worker's environment: the request's extra env
+ *  with the manager's `KILD_*` vars on top. The worker also inherits the engine's own
+ *  environment, which is not part of this (and not recorded — it may hold secrets). */
+export function sessionEnv(
+  id: string,
+  req: SpawnRequest,
+  skillsProfile: string | undefined,
+): Record<string, string> {
+  return { ...req.env, ...workerEnv(id, req, skillsProfile) };
+}
+
 /** Control-line callbacks for a session's worker — used by the RoomManager to route
  *  a participant's `post_message` / `invite_agent` back into its room. A bare
  *  (non-room) session passes none, so the control lines are simply never emitted. */
@@ -109,26 +125,22 @@ export interface SessionCallbacks {
 class PiSession {
   private readonly child: ChildProcess;
   private buf = '';
+  // A multibyte character can straddle two reads; the decoder holds its first bytes back.
+  private readonly decoder = new StringDecoder('utf8');
 
   constructor(
-    id: string,
-    req: SpawnRequest,
+    env: Record<string, string>,
     onEvent: (event: UiEvent) => void,
     callbacks?: SessionCallbacks,
   ) {
     const { KILD_SKILLS_PROFILE: _inheritedSkillsProfile, ...parentEnv } = process.env;
-    const skillsProfile = skillsProfileForWorker(req.env?.KILD_ROOM, SKILLS_PROFILE);
     this.child = spawn(process.argv[0] as string, process.argv.slice(1), {
-      env: {
-        ...parentEnv,
-        ...req.env, // extra worker env (e.g. room membership); our KILD_* win below
-        ...workerEnv(id, req, skillsProfile),
-      },
+      env: { ...parentEnv, ...env }, // the injected env (sessionEnv) wins over inherited
       stdio: ['pipe', 'pipe', 'inherit'],
     });
 
     this.child.stdout?.on('data', (chunk: Buffer) => {
-      this.buf += chunk.toString();
+      this.buf += this.decoder.write(chunk);
       const lines = this.buf.split('\n');
       this.buf = lines.pop() ?? ''; // keep the incomplete trailing line
       for (const raw of lines) {
@@ -198,7 +210,10 @@ class PiSession {
  * same broadcast, so a session started anywhere is visible everywhere.
  */
 export class SessionManager {
-  private readonly sessions = new Map<string, { session: PiSession; info: SessionInfo }>();
+  private readonly sessions = new Map<
+    string,
+    { session: PiSession; info: SessionInfo; env: Record<string, string> }
+  >();
   private readonly subscribers = new Set<(msg: Outbound) => void>();
 
   subscribe(fn: (msg: Outbound) => void): () => void {
@@ -213,6 +228,12 @@ export class SessionManager {
     return [...this.sessions.values()].map((s) => s.info);
   }
 
+  /** The environment a live session's worker was spawned with ({@link sessionEnv}) —
+   *  read-only, for debugging what an agent actually received. Undefined if not live. */
+  env(id: string): Record<string, string> | undefined {
+    return this.sessions.get(id)?.env;
+  }
+
   resolveActor(id: string): CommandResult<string> {
     const info = this.sessions.get(id)?.info;
     if (!info) return { ok: false, code: 'rejected', message: `unknown session: ${id}` };
@@ -248,7 +269,13 @@ export class SessionManager {
       try {
         info.worktree = req.worktree;
         info.branch = worktreeRef(req.worktree);
-        info.worktreePath = worktreePath(req.worktree);
+        const repo = req.cwd ?? process.cwd();
+        info.worktreePath = worktreePath(req.worktree, repo);
+        // An existing tree may be elsewhere (an earlier worktreeRoot, a legacy dir) —
+        // the worker attaches to it there, so report that path once git says so.
+        void locateWorktree(repo, req.worktree).then((found) => {
+          info.worktreePath = found;
+        });
       } catch (err) {
         this.broadcast({
           session: id,
@@ -258,10 +285,11 @@ export class SessionManager {
         return;
       }
     }
+    const env = sessionEnv(id, req, skillsProfileForWorker(req.env?.KILD_ROOM, SKILLS_PROFILE));
     const session = new PiSession(
-      id,
-      req,
+      env,
       (event) => {
+        info.lastActivity = Date.now();
         // Capture the pi session's durable identity so any client can offer a
         // terminal resume (`pi --session …`) for this agent.
         if (event.kind === 'pi_session') {
@@ -277,7 +305,7 @@ export class SessionManager {
       },
       callbacks,
     );
-    this.sessions.set(id, { session, info });
+    this.sessions.set(id, { session, info, env });
     this.broadcast({ sessions: this.list() });
   }
 
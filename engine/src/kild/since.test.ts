@@ -0,0 +1,57 @@
+import { expect, test } from 'bun:test';
+
+import type { ReviewCommit } from './git-review.ts';
+import { commitsSince, isIdleSince, parseDuration, parseSince } from './since.ts';
+
+const NOW = Date.parse('2026-10-16T12:00:00Z');
+
+const commit = (sha: string, ts: number): ReviewCommit => ({
+  sha,
+  subject: sha,
+  author: 't',
+  ts,
+  filesChanged: 1,
+  additions: 1,
+  deletions: 0,
+});
+
+test('relative durations count back from now; ISO dates parse as-is', () => {
+  expect(parseSince('30m', NOW)).toBe(NOW - 30 * 60_000);
+  expect(parseSince('2h', NOW)).toBe(NOW - 2 * 3_600_000);
+  expect(parseSince('1d', NOW)).toBe(NOW - 86_400_000);
+  expect(parseSince('1w', NOW)).toBe(NOW - 7 * 86_400_000);
+  expect(parseSince('2026-10-01T09:00:00Z', NOW)).toBe(Date.parse('2026-10-01T09:00:00Z'));
+});
+
+test('anything else is not a cutoff', () => {
+  expect(parseSince('5', NOW)).toBeUndefined();
+  expect(parseSince('1y', NOW)).toBeUndefined();
+  expect(parseSince('yesterday', NOW)).toBeUndefined();
+  expect(parseSince('', NOW)).toBeUndefined();
+});
+
+test('only commits at or after the cutoff are kept', () => {
+  const since = parseSince('1h', NOW) as number;
+  const commits = [
+    commit('old', since - 1),
+    commit('edge', since),
+    commit('new', NOW - 60_000),
+  ];
+
+  expect(commitsSince(commits, since).map((c) => c.sha)).toEqual(['edge', 'new']);
+});
+
+test('durations parse to millis; a date is not a duration', () => {
+  expect(parseDuration('45m')).toBe(45 * 60_000);
+  expect(parseDuration('3d')).toBe(3 * 86_400_000);
+  expect(parseDuration('2026-10-01')).toBeUndefined();
+});
+
+test('a session is idle when its last activity predates the cutoff, or it has none', () => {
+  const cutoff = NOW - 3_600_000;
+
+  expect(isIdleSince(cutoff - 1, cutoff)).toBe(true);
+  expect(isIdleSince(cutoff, cutoff)).toBe(false);
+  expect(isIdleSince(NOW, cutoff)).toBe(false);
+  expect(isIdleSince(undefined, cutoff)).toBe(true);
+});
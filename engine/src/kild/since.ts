@@ -0,0 +1,75 @@
+import { execFile as execFileCb } from 'node:child_process';
+import { promisify } from 'node:util';
+
+import { type ReviewCommit, reviewCommits } from './git-review.ts';
+import { worktreeName } from './worktree.ts';
+
+const execFile = promisify(execFileCb);
+
+const UNIT_MS: Record<string, number> = {
+  m: 60_000,
+  h: 3_600_000,
+  d: 86_400_000,
+  w: 604_800_000,
+};
+
+/** Parse a duration (`30m`, `1h`, `2d`, `1w`) into millis. Undefined for anything else. */
+export function parseDuration(input: string): number | undefined {
+  const match = /^(\d+)([mhdw])$/.exec(input.trim());
+  if (!match) return undefined;
+  return Number.parseInt(match[1] as string, 10) * (UNIT_MS[match[2] as string] ?? 0);
+}
+
+/** Parse a `kild since` cutoff into epoch millis: a {@link parseDuration} back from
+ *  `now`, or an ISO date/time (`2026-10-01`, `2026-10-01T09:00`). Undefined when it
+ *  is neither. */
+export function parseSince(input: string, now = Date.now()): number | undefined {
+  const duration = parseDuration(input);
+  if (duration !== undefined) return now - duration;
+  // Dates only: a bare number like `5` would otherwise parse as a year.
+  const abs = /^\d{4}-\d{2}-\d{2}/.test(input.trim()) ? Date.parse(input) : Number.NaN;
+  return Number.isNaN(abs) ? undefined : abs;
+}
+
+/** The commits made at or after `since` (epoch millis). */
+export function commitsSince(commits: ReviewCommit[], since: number): ReviewCommit[] {
+  return commits.filter((commit) => commit.ts >= since);
+}
+
+/** Has nothing happened since `cutoff` (epoch millis)? A session with no recorded
+ *  activity (`lastActivity` undefined) counts as idle for any cutoff. */
+export function isIdleSince(lastActivity: number | undefined, cutoff: number): boolean {
+  return lastActivity === undefined || lastActivity < cutoff;
+}
+
+export interface WorkstreamActivity {
+  name: string;
+  branch: string;
+  commits: ReviewCommit[];
+}
+
+/** What landed on each `kild/*` branch of `repo` since `since` — commits vs `base`,
+ *  read by ref from the main checkout, so a branch whose tree was removed still
+ *  counts. Branches with nothing new are left out. */
+export async function activitySince(
+  repo: string,
+  since: number,
+  base: string,
+): Promise<WorkstreamActivity[]> {
+  const { stdout } = await execFile('git', [
+    '-C',
+    repo,
+    'for-each-ref',
+    '--format=%(refname:short)',
+    'refs/heads/kild/',
+  ]);
+  const activity: WorkstreamActivity[] = [];
+  for (const branch of stdout.split('\n').map((line) => line.trim())) {
+    if (!branch) continue;
+    const { commits, error } = await reviewCommits(repo, base, branch);
+    if (error) throw new Error(`${branch}: ${error}`);
+    const recent = commitsSince(commits, since);
+    if (recent.length) activity.push({ name: worktreeName(branch), branch, commits: recent });
+  }
+  return activity;
+}
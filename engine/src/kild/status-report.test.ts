@@ -0,0 +1,128 @@
+import { afterEach, beforeEach, expect, test } from 'bun:test';
+import { execFile as execFileCb } from 'node:child_process';
+import { mkdtempSync, rmSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+
+import {
+  reportPart,
+  worktreeListEntries,
+  worktreeListEntry,
+  worktreeStatusReport,
+} from './status-report.ts';
+import { ensureWorktree } from './worktree.ts';
+
+const execFile = promisify(execFileCb);
+
+let repo: string;
+let home: string;
+let prevHome: string | undefined;
+
+const git = (...args: string[]) => execFile('git', ['-C', repo, ...args]);
+
+beforeEach(async () => {
+  repo = mkdtempSync(path.join(tmpdir(), 'kild-report-repo-'));
+  home = mkdtempSync(path.join(tmpdir(), 'kild-report-home-'));
+  prevHome = process.env.KILD_HOME;
+  process.env.KILD_HOME = home; // worktrees live under $KILD_HOME/worktrees
+  await git('init', '-q', '-b', 'main');
+  await git('config', 'user.email', 't@t');
+  await git('config', 'user.name', 't');
+  await git('commit', '-q', '--allow-empty', '-m', 'init');
+});
+
+afterEach(() => {
+  if (prevHome === undefined) delete process.env.KILD_HOME;
+  else process.env.KILD_HOME = prevHome;
+  rmSync(repo, { recursive: true, force: true });
+  rmSync(home, { recursive: true, force: true });
+});
+
+test('a part that throws or yields nothing is unavailable, with the reason', async () => {
+  expect(await reportPart(async () => 1, 'n/a')).toEqual({ ok: true, value: 1 });
+  expect(await reportPart(async () => undefined, 'n/a')).toEqual({ ok: false, unavailable: 'n/a' });
+  expect(
+    await reportPart(async () => {
+      throw new Error('boom');
+    }, 'n/a'),
+  ).toEqual({ ok: false, unavailable: 'boom' });
+});
+
+test('a live worktree reports git state; no remote leaves only the PR unavailable', async () => {
+  const wt = await ensureWorktree(repo, 'feat');
+  await execFile('git', ['-C', wt.path, 'commit', '-q', '--allow-empty', '-m', 'work']);
+
+  const report = await worktreeStatusReport(repo, 'feat', 'main');
+
+  expect(report.branch).toBe('kild/feat');
+  expect(report.git.ok && report.git.value.ahead).toBe(1);
+  expect(report.pushed).toEqual({ ok: true, value: false });
+  expect(report.pr).toEqual({ ok: false, unavailable: 'origin remote is not on a known forge' });
+  expect(report.ci.ok).toBe(false);
+});
+
+test('a removed worktree degrades its git part but still reports the rest', async () => {
+  const report = await worktreeStatusReport(repo, 'gone', 'main');
+
+  expect(report.git).toEqual({ ok: false, unavailable: 'no worktree (removed, or never created)' });
+  expect(report.pushed.ok).toBe(true);
+});
+
+test('a list entry has every key, with null for each part that could not be read', () => {
+  const entry = worktreeListEntry({
+    name: 'gone',
+    branch: 'kild/gone',
+    path: '/wt/gone',
+    git: { ok: false, unavailable: 'no worktree' },
+    pushed: { ok: true, value: true },
+    pr: { ok: false, unavailable: 'PR lookup failed' },
+    ci: { ok: false, unavailable: 'CI lookup failed' },
+  });
+
+  expect(entry).toEqual({
+    name: 'gone',
+    branch: 'kild/gone',
+    path: '/wt/gone',
+    dirty: null,
+    ahead: null,
+    behind: null,
+    pushed: true,
+    prState: null,
+    prUrl: null,
+    ciState: null,
+  });
+});
+
+test('list entries carry the git state, and a branch without a PR reads as none', async () => {
+  const wt = await ensureWorktree(repo, 'feat');
+  await execFile('git', ['-C', wt.path, 'commit', '-q', '--allow-empty', '-m', 'work']);
+
+  const [entry] = await worktreeListEntries(repo, [wt], 'main');
+
+  expect(entry).toMatchObject({ name: 'feat', dirty: false, ahead: 1, behind: 0, pushed: false });
+  // No forge call without asking for one: the PR and CI are null, not 'none'.
+  expect(entry).toMatchObject({ prState: null, prUrl: null, ciState: null });
+  expect(
+    worktreeListEntry({
+      name: 'x',
+      branch: 'kild/x',
+      path: '/x',
+      git: { ok: false, unavailable: '' },
+      pushed: { ok: false, unavailable: '' },
+      pr: { ok: true, value: null },
+      ci: { ok: true, value: 'none' },
+    }).prState,
+  ).toBe('none');
+});
+
+test('list entries read each tree where git has it, keeping its Worktree fields', async () => {
+  const wt = await ensureWorktree(repo, 'feat');
+  const moved = path.join(home, 'elsewhere');
+  await git('worktree', 'move', wt.path, moved);
+  await execFile('git', ['-C', moved, 'commit', '-q', '--allow-empty', '-m', 'work']);
+
+  const [entry] = await worktreeListEntries(repo, [{ ...wt, path: moved }], 'main');
+
+  expect(entry).toMatchObject({ name: 'feat', branch: 'kild/feat', path: moved, ahead: 1 });
+});
@@ -0,0 +1,144 @@
+import { existsSync } from 'node:fs';
+
+import { configuredDirtyPolicy } from './config.ts';
+import {
+  type CiState,
+  findCiStatusCached,
+  findPrCached,
+  type PrInfo,
+  type PrState,
+  repoForge,
+} from './forge.ts';
+import { mapLimit } from './map-limit.ts';
+import { locateWorktree, type Worktree, worktreeName, worktreeRef } from './worktree.ts';
+import {
+  branchIsFullyPushed,
+  type WorkstreamGitStatus,
+  workstreamGitStatus,
+} from './worktree-status.ts';
+
+/** One part of a {@link WorktreeStatusReport}: its value, or why it couldn't be read. */
+export type ReportPart<T> = { ok: true; value: T } | { ok: false; unavailable: string };
+
+/** Everything about one kild worktree in one view (`kild worktree status <name>`):
+ *  its git state vs base, whether every commit is pushed, its PR, and that PR's CI
+ *  (`none` without a PR). Each part is read
+ *  independently — a missing tree or an unknown forge marks that part unavailable, it
+ *  never fails the report. */
+export interface WorktreeStatusReport {
+  name: string;
+  branch: string;
+  path: string;
+  git: ReportPart<WorkstreamGitStatus>;
+  pushed: ReportPart<boolean>;
+  pr: ReportPart<PrInfo | null>;
+  ci: ReportPart<CiState>;
+}
+
+/** Await one part, turning a throw or an `undefined` result into `unavailable`. */
+export async function reportPart<T>(
+  read: () => Promise<T | undefined>,
+  whyUndefined: string,
+): Promise<ReportPart<T>> {
+  try {
+    const value = await read();
+    return value === undefined ? { ok: false, unavailable: whyUndefined } : { ok: true, value };
+  } catch (err) {
+    return { ok: false, unavailable: err instanceof Error ? err.message : String(err) };
+  }
+}
+
+/** How {@link worktreeStatusReport} reads a tree. `path`: where the tree is, when the
+ *  caller already listed it (else it is located). `forge: false` skips the PR and CI
+ *  lookups — a forge call per tree — leaving both parts unavailable. */
+export interface StatusReportOptions {
+  path?: string;
+  forge?: boolean;
+}
+
+const FORGE_SKIPPED = 'not looked up';
+
+/** Build the {@link WorktreeStatusReport} for worktree `name` of `repo`, vs `base`. */
+export async function worktreeStatusReport(
+  repo: string,
+  name: string,
+  base: string,
+  opts: StatusReportOptions = {},
+): Promise<WorktreeStatusReport> {
+  const branch = worktreeRef(name);
+  const wtPath = opts.path ?? (await locateWorktree(repo, name));
+  const lookUp = opts.forge ?? true;
+  const forge = lookUp ? await repoForge(repo) : undefined;
+  const [git, pushed, pr] = await Promise.all([
+    reportPart(async () => {
+      if (!existsSync(wtPath)) return undefined;
+      const status = await workstreamGitStatus(wtPath, base, await configuredDirtyPolicy(repo));
+      if (status.error) throw new Error(status.error);
+      return status;
+    }, 'no worktree (removed, or never created)'),
+    reportPart(() => branchIsFullyPushed(repo, branch), 'unknown'),
+    reportPart(async () => {
+      if (!lookUp) throw new Error(FORGE_SKIPPED);
+      if (!forge) throw new Error('origin remote is not on a known forge');
+      return findPrCached(repo, forge, branch);
+    }, 'PR lookup failed'),
+  ]);
+  const ci = await reportPart(async () => {
+    if (!lookUp) throw new Error(FORGE_SKIPPED);
+    if (!pr.ok) throw new Error(`PR unavailable (${pr.unavailable})`);
+    if (!pr.value || !forge) return 'none';
+    return findCiStatusCached(repo, forge, branch, pr.value);
+  }, 'CI lookup failed');
+  return { name, branch, path: wtPath, git, pushed, pr, ci };
+}
+
+/** One row of `kild worktree ls --json` — a flat, stable shape for scripts and
+ *  dashboards: the {@link Worktree} fields plus its status. Every key is always present;
+ *  a value that couldn't be read (or, for the PR and CI, wasn't asked for) is `null` —
+ *  never omitted, never guessed. `prState` is `none` when the branch has no PR. */
+export interface WorktreeListEntry extends Required<Worktree> {
+  dirty: boolean | null;
+  ahead: number | null;
+  behind: number | null;
+  pushed: boolean | null;
+  prState: PrState | 'none' | null;
+  prUrl: string | null;
+  ciState: CiState | null;
+}
+
+/** Flatten a {@link WorktreeStatusReport} into its {@link WorktreeListEntry}. */
+export function worktreeListEntry(report: WorktreeStatusReport): WorktreeListEntry {
+  const git = report.git.ok ? report.git.value : undefined;
+  const pr = report.pr.ok ? report.pr.value : undefined;
+  return {
+    name: report.name,
+    branch: report.branch,
+    path: report.path,
+    dirty: git?.dirty ?? null,
+    ahead: git?.ahead ?? null,
+    behind: git?.behind ?? null,
+    pushed: report.pushed.ok ? report.pushed.value : null,
+    prState: pr === undefined ? null : (pr?.state ?? 'none'),
+    prUrl: pr?.url ?? null,
+    ciState: report.ci.ok ? report.ci.value : null,
+  };
+}
+
+/** Status reads in flight at once — each is a few git processes (and maybe a forge call). */
+const STATUS_CONCURRENCY = 4;
+
+/** {@link worktreeListEntry} for each of `repo`'s listed kild `trees`, in order, each
+ *  read at the path git has it. The PR and CI are looked up only with `forge`. */
+export async function worktreeListEntries(
+  repo: string,
+  trees: Worktree[],
+  base: string,
+  forge = false,
+): Promise<WorktreeListEntry[]> {
+  return mapLimit(trees, STATUS_CONCURRENCY, async (tree) => {
+    const name = tree.name ?? worktreeName(tree.branch);
+    return worktreeListEntry(
+      await worktreeStatusReport(repo, name, base, { path: tree.path, forge }),
+    );
+  });
+}
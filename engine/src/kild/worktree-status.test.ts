@@ -5,7 +5,12 @@ import os from 'node:os';
 import path from 'node:path';
 import { promisify } from 'node:util';
 
-import { workstreamGitStatus } from './worktree-status.ts';
+import {
+  branchIsFullyPushed,
+  countBranchStashes,
+  isFullyPushed,
+  workstreamGitStatus,
+} from './worktree-status.ts';
 
 const execFile = promisify(execFileCb);
 
@@ -97,6 +102,54 @@ test('an uncommitted edit marks the workstream dirty', async () => {
   expect(status.changedFiles).toEqual([]);
 });
 
+test('an untracked file is dirty unless ignoreUntracked is set', async () => {
+  const dir = await initRepo();
+  fs.writeFileSync(path.join(dir, 'build.out'), 'artifact\n');
+
+  const counted = await workstreamGitStatus(dir, 'main');
+  const ignored = await workstreamGitStatus(dir, 'main', { ignoreUntracked: true });
+
+  expect(counted.dirty).toBe(true);
+  expect(counted.uncommittedFiles).toBe(1);
+  expect(ignored.dirty).toBe(false);
+  expect(ignored.uncommittedFiles).toBe(0);
+});
+
+test('changes only in ignoreGlobs paths are clean; a source edit is still dirty', async () => {
+  const dir = await initRepo();
+  fs.writeFileSync(path.join(dir, 'notes.local.md'), 'scratch\n');
+  const policy = { ignoreGlobs: ['*.local.md'] };
+
+  const onlyIgnored = await workstreamGitStatus(dir, 'main', policy);
+  fs.writeFileSync(path.join(dir, 'README.md'), 'changed\n');
+  const withSource = await workstreamGitStatus(dir, 'main', policy);
+
+  expect(onlyIgnored.dirty).toBe(false);
+  expect(onlyIgnored.uncommittedFiles).toBe(0);
+  expect(withSource.dirty).toBe(true);
+  expect(withSource.uncommittedFiles).toBe(1);
+});
+
+test('stashes are counted per branch from their reflog subjects', () => {
+  const list = ['On kild/a: note: with colon', 'WIP on main: abc1234 init', 'WIP on kild/a: x'];
+
+  expect(countBranchStashes(list.join('\n'), 'kild/a')).toBe(2);
+  expect(countBranchStashes(list.join('\n'), 'kild/ab')).toBe(0);
+  expect(countBranchStashes('', 'main')).toBe(0);
+});
+
+test('a stash on the branch is counted even when the tree is otherwise clean', async () => {
+  const dir = await initRepo();
+  await git(dir, ['checkout', '-q', '-b', 'feature']);
+  fs.writeFileSync(path.join(dir, 'README.md'), 'stashed\n');
+  await git(dir, ['-c', 'user.email=t@t', '-c', 'user.name=t', 'stash', '-q']);
+
+  const status = await workstreamGitStatus(dir, 'main');
+
+  expect(status.dirty).toBe(false);
+  expect(status.stashCount).toBe(1);
+});
+
 test('a non-git directory returns an error object without throwing', async () => {
   const dir = mkTmp('kild-wt-nogit-');
 
@@ -158,3 +211,26 @@ test('a missing base ref is reported as an error, not a crash', async () => {
   expect(status.behind).toBe(0);
   expect(status.changedFiles).toEqual([]);
 });
+
+test('fully pushed needs a remote counterpart and nothing ahead of it', () => {
+  expect(isFullyPushed({ hasRemote: true, ahead: 0 })).toBe(true);
+  expect(isFullyPushed({ hasRemote: true, ahead: 2 })).toBe(false);
+  expect(isFullyPushed({ hasRemote: false, ahead: 0 })).toBe(false);
+});
+
+test('a branch is fully pushed until it gains a local-only commit', async () => {
+  const dir = await initRepo();
+  const remote = mkTmp('kild-wt-remote-');
+  await execFile('git', ['init', '-q', '--bare', remote]);
+  await git(dir, ['checkout', '-q', '-b', 'feature']);
+  expect(await branchIsFullyPushed(dir, 'feature')).toBe(false); // no remote yet
+
+  await git(dir, ['remote', 'add', 'origin', remote]);
+  await git(dir, ['push', '-q', '-u', 'origin', 'feature']);
+  expect(await branchIsFullyPushed(dir, 'feature')).toBe(true);
+
+  fs.writeFileSync(path.join(dir, 'local.txt'), 'x\n');
+  await git(dir, ['add', '.']);
+  await commit(dir, 'local only');
+  expect(await branchIsFullyPushed(dir, 'feature')).toBe(false);
+});
@@ -1,6 +1,9 @@
 import { execFile as execFileCb } from 'node:child_process';
 import { promisify } from 'node:util';
 
+import type { DirtyPolicy } from './config.ts';
+import { matchesAnyGlob } from './glob.ts';
+
 // execFile (no shell) mirrors worktree.ts: `dir`/`base` may originate from an
 // LLM-driven workstream selector, so shell interpolation would be RCE. This module
 // is pure observability — a driving agent reads each workstream's git state through
@@ -23,6 +26,7 @@ export interface WorkstreamGitStatus {
   uncommittedFiles: number;
   changedFiles: string[]; // files changed vs base (committed): git diff --name-only <base>...HEAD
   conflictsWithBase: boolean | null; // would HEAD merge into base cleanly? null = undetermined
+  stashCount?: number; // stashes made on this branch; absent = undetermined, never a guessed 0
   error?: string; // any git failure captured here, NEVER thrown
 }
 
@@ -51,12 +55,15 @@ export async function resolveDefaultBase(dir: string): Promise<string> {
 }
 
 /** Inspect one workstream directory's git state relative to `base` (default: the
- *  remote default branch, else `main`). Never throws: a non-git dir, a missing base
- *  ref, or any git error returns a well-formed object with `error` set and safe
- *  defaults so a driving agent can surface the state without crashing. */
+ *  remote default branch, else `main`). `dirty` leaves untracked files and/or paths
+ *  matching `ignoreGlobs` out of `dirty`/`uncommittedFiles` (the configured
+ *  {@link DirtyPolicy}). Never throws: a non-git dir, a missing base ref, or any git
+ *  error returns a well-formed object with `error` set and safe defaults so a driving
+ *  agent can surface the state without crashing. */
 export async function workstreamGitStatus(
   dir: string,
   base?: string,
+  dirty: Partial<DirtyPolicy> = {},
 ): Promise<WorkstreamGitStatus> {
   const resolvedBase = base ?? (await resolveDefaultBase(dir));
   const status: WorkstreamGitStatus = {
@@ -79,12 +86,22 @@ export async function workstreamGitStatus(
     return status;
   }
   status.branch = branch.stdout.trim() || null;
+  if (status.branch) status.stashCount = await branchStashCount(dir, status.branch);
 
   // Working-tree cleanliness is base-independent, so report it even when the base
   // ref is missing below. Any porcelain line means dirty; the line count is the file count.
-  const porcelain = await runGit(dir, ['status', '--porcelain']);
+  const porcelain = await runGit(dir, [
+    'status',
+    '--porcelain',
+    ...(dirty.ignoreUntracked ? ['--untracked-files=no'] : []),
+  ]);
   if (porcelain.ok) {
-    const lines = porcelain.stdout.split('\n').filter((line) => line.length > 0);
+    // `XY path`, or `XY old -> new` for a rename — the path that matters is the last.
+    const ignoreGlobs = dirty.ignoreGlobs ?? [];
+    const lines = porcelain.stdout
+      .split('\n')
+      .filter((line) => line.length > 0)
+      .filter((line) => !matchesAnyGlob(line.slice(3).split(' -> ').pop() ?? '', ignoreGlobs));
     status.uncommittedFiles = lines.length;
     status.dirty = lines.length > 0;
   } else {
@@ -148,3 +165,39 @@ export async function workstreamGitStatus(
 
   return status;
 }
+
+/** How many entries of `git stash list --format=%gs` were made on `branch`. The stash
+ *  is shared by every worktree of a repo; each entry's reflog subject (`WIP on <b>: …`
+ *  or `On <b>: …`) names the branch it came from. */
+export function countBranchStashes(stdout: string, branch: string): number {
+  return stdout
+    .split('\n')
+    .filter((line) => /^(?:WIP on|On) (.+?): /.exec(line)?.[1] === branch).length;
+}
+
+/** {@link countBranchStashes} for `repo`. Undefined when the stash can't be read —
+ *  a failed probe must not read as "nothing stashed". Never throws. */
+export async function branchStashCount(repo: string, branch: string): Promise<number | undefined> {
+  const list = await runGit(repo, ['stash', 'list', '--format=%gs']);
+  return list.ok ? countBranchStashes(list.stdout, branch) : undefined;
+}
+
+/** Is every commit on a branch also on its remote? True only with a remote-tracking
+ *  counterpart AND nothing ahead of it — a branch never pushed has no copy anywhere
+ *  else, however few commits it has. */
+export function isFullyPushed(remote: { hasRemote: boolean; ahead: number }): boolean {
+  return remote.hasRemote && remote.ahead === 0;
+}
+
+/** {@link isFullyPushed} for `branch` in `repo`: compared against its upstream, else
+ *  `origin/<branch>`. The guard before a destructive branch op (e.g. `-D`) — false
+ *  means deleting it loses commits for good. Never throws: an unknown state is false. */
+export async function branchIsFullyPushed(repo: string, branch: string): Promise<boolean> {
+  const upstream = await runGit(repo, ['rev-parse', '--abbrev-ref', `${branch}@{upstream}`]);
+  const remote = upstream.ok ? upstream.stdout.trim() : `origin/${branch}`;
+  const exists = await runGit(repo, ['rev-parse', '--verify', '--quiet', `${remote}^{commit}`]);
+  if (!exists.ok) return isFullyPushed({ hasRemote: false, ahead: 0 });
+  const ahead = await runGit(repo, ['rev-list', '--count', `${remote}..${branch}`]);
+  if (!ahead.ok) return false;
+  return isFullyPushed({ hasRemote: true, ahead: Number.parseInt(ahead.stdout, 10) || 0 });
+}
@@ -6,10 +6,18 @@ import path from 'node:path';
 import { promisify } from 'node:util';
 
 import {
+  abortRebase,
+  adoptWorktree,
   ensureWorktree,
+  findWorktree,
   forceRemoveWorktree,
+  forkWorktree,
+  isGitRepo,
   pruneMergedWorktrees,
+  rebaseWorktree,
   removeWorktree,
+  renameWorktree,
+  restoreWorktree,
   worktreePath,
 } from './worktree.ts';
 
@@ -111,6 +119,33 @@ test('safe removal refuses dirty worktree and previews modified and untracked fi
   expect(existsSync(wt.path)).toBe(true);
 });
 
+test('dirtyIgnoresUntracked lets safe removal take a tree with only untracked files', async () => {
+  const wt = await ensureWorktree(repo, 'artifacts');
+  writeFileSync(path.join(wt.path, 'build.out'), 'artifact');
+  expect(await removeWorktree(repo, wt.path)).toMatchObject({ ok: false, code: 'dirty' });
+
+  mkdirSync(path.join(repo, '.kild'));
+  writeFileSync(path.join(repo, '.kild', 'config.json'), '{"dirtyIgnoresUntracked": true}');
+  await expect(removeWorktree(repo, wt.path)).resolves.toEqual({ ok: true });
+  expect(existsSync(wt.path)).toBe(false);
+});
+
+test('cleanIgnoreGlobs paths do not block safe removal; other changes still do', async () => {
+  mkdirSync(path.join(repo, '.kild'));
+  writeFileSync(path.join(repo, '.kild', 'config.json'), '{"cleanIgnoreGlobs": ["*.local"]}');
+  const wt = await ensureWorktree(repo, 'local-files');
+  writeFileSync(path.join(wt.path, 'me.local'), 'mine');
+  writeFileSync(path.join(wt.path, 'src.ts'), 'work');
+
+  expect(await removeWorktree(repo, wt.path)).toEqual({
+    ok: false,
+    code: 'dirty',
+    files: ['src.ts'],
+  });
+  rmSync(path.join(wt.path, 'src.ts'));
+  await expect(removeWorktree(repo, wt.path)).resolves.toEqual({ ok: true });
+});
+
 test('force removal discards a dirty worktree', async () => {
   const wt = await ensureWorktree(repo, 'force-dirty');
   writeFileSync(path.join(wt.path, 'WIP.txt'), 'discard');
@@ -136,7 +171,280 @@ test('ensureWorktree re-creating a removed worktree preserves the branch commits
   expect(existsSync(path.join(again.path, 'COMMITTED.txt'))).toBe(true);
 });
 
+test('ensureWorktree recovers from a registered worktree whose dir was deleted', async () => {
+  const wt = await ensureWorktree(repo, 'vanished');
+  await gitIn(wt.path, 'commit', '-q', '--allow-empty', '-m', 'work');
+  rmSync(wt.path, { recursive: true, force: true }); // behind git's back: still registered
+
+  const again = await ensureWorktree(repo, 'vanished');
+
+  expect(existsSync(path.join(again.path, '.git'))).toBe(true);
+  expect((await gitIn(again.path, 'log', '-1', '--format=%s')).stdout.trim()).toBe('work');
+});
+
+test('ensureWorktree names an unwritable worktree root instead of a git error', async () => {
+  const blocker = path.join(home, 'not-a-dir');
+  writeFileSync(blocker, '');
+  writeFileSync(path.join(home, 'config.json'), JSON.stringify({ worktreeRoot: blocker }));
+
+  await expect(ensureWorktree(repo, 'x')).rejects.toThrow(
+    `worktree root is not writable: ${blocker}`,
+  );
+});
+
+test('a configured worktree root that does not exist is an error, never created', async () => {
+  const unmounted = path.join(home, 'mnt', 'fast');
+  writeFileSync(path.join(home, 'config.json'), JSON.stringify({ worktreeRoot: unmounted }));
+
+  await expect(ensureWorktree(repo, 'x')).rejects.toThrow(
+    `worktree root is not writable: ${unmounted}`,
+  );
+  expect(existsSync(path.join(home, 'mnt'))).toBe(false);
+});
+
+test("a project's worktreeRoot places that project's new trees", async () => {
+  const trees = path.join(home, 'project-trees');
+  mkdirSync(trees);
+  mkdirSync(path.join(repo, '.kild'), { recursive: true });
+  writeFileSync(path.join(repo, '.kild', 'config.json'), JSON.stringify({ worktreeRoot: trees }));
+
+  const wt = await ensureWorktree(repo, 'feat');
+
+  expect(wt.path).toBe(path.join(trees, 'feat'));
+  expect(await findWorktree(repo, 'feat')).toMatchObject({ path: wt.path });
+});
+
+test('a tree made before worktreeRoot changed is still found and operated on', async () => {
+  const wt = await ensureWorktree(repo, 'before');
+  const moved = path.join(home, 'elsewhere');
+  mkdirSync(moved);
+  writeFileSync(path.join(home, 'config.json'), JSON.stringify({ worktreeRoot: moved }));
+
+  expect(await findWorktree(repo, 'before')).toMatchObject({ path: wt.path });
+  expect((await ensureWorktree(repo, 'before')).path).toBe(wt.path);
+  expect(await rebaseWorktree(repo, 'before', 'main')).toEqual({ ok: true, status: 'up_to_date' });
+  const renamed = await renameWorktree(repo, 'before', 'after');
+  expect(renamed).toEqual({
+    ok: true,
+    worktree: { branch: 'kild/after', path: path.join(moved, 'after'), name: 'after' },
+  });
+  expect(existsSync(wt.path)).toBe(false);
+});
+
 test('ensureWorktree throws on a stale non-worktree dir (no silent non-isolated cwd)', async () => {
   mkdirSync(worktreePath('stale'), { recursive: true });
   await expect(ensureWorktree(repo, 'stale')).rejects.toThrow();
 });
+
+test('adoptWorktree moves a hand-made tree onto kild/<branch> at the derived path', async () => {
+  const manual = path.join(home, 'worktrees', 'by-hand');
+  await git('worktree', 'add', '-q', '-b', 'feature', manual);
+  writeFileSync(path.join(manual, 'WIP.txt'), 'uncommitted');
+
+  const adopted = await adoptWorktree(repo, manual);
+
+  expect(adopted).toEqual({
+    branch: 'kild/feature',
+    path: worktreePath('feature'),
+    name: 'feature',
+  });
+  expect(existsSync(manual)).toBe(false);
+  expect(existsSync(path.join(adopted.path, 'WIP.txt'))).toBe(true);
+  expect((await gitIn(adopted.path, 'symbolic-ref', '--short', 'HEAD')).stdout.trim()).toBe(
+    'kild/feature',
+  );
+});
+
+test('adoptWorktree refuses the main checkout and an existing kild worktree', async () => {
+  await expect(adoptWorktree(repo, repo)).rejects.toThrow('main checkout');
+  const wt = await ensureWorktree(repo, 'owned');
+  await expect(adoptWorktree(repo, wt.path)).rejects.toThrow('already a kild worktree');
+});
+
+test('renameWorktree moves the branch and tree, keeping commits and uncommitted work', async () => {
+  const wt = await ensureWorktree(repo, 'wrong-name');
+  await gitIn(wt.path, 'commit', '-q', '--allow-empty', '-m', 'work');
+  writeFileSync(path.join(wt.path, 'WIP.txt'), 'uncommitted');
+
+  const result = await renameWorktree(repo, 'wrong-name', 'right-name');
+
+  expect(result).toEqual({
+    ok: true,
+    worktree: { branch: 'kild/right-name', path: worktreePath('right-name'), name: 'right-name' },
+  });
+  expect(existsSync(wt.path)).toBe(false);
+  expect(existsSync(path.join(worktreePath('right-name'), 'WIP.txt'))).toBe(true);
+  const { stdout } = await git('log', '-1', '--format=%s', 'kild/right-name');
+  expect(stdout.trim()).toBe('work');
+  expect((await git('branch')).stdout).not.toContain('kild/wrong-name');
+});
+
+test('renameWorktree refuses taken, missing, in-use, and invalid names', async () => {
+  await ensureWorktree(repo, 'a');
+  await ensureWorktree(repo, 'b');
+
+  expect(await renameWorktree(repo, 'a', 'b')).toEqual({ ok: false, code: 'exists' });
+  expect(await renameWorktree(repo, 'nope', 'c')).toEqual({ ok: false, code: 'not_found' });
+  expect(await renameWorktree(repo, 'a', 'c', true)).toEqual({ ok: false, code: 'in_use' });
+  await expect(renameWorktree(repo, 'a', '-c')).rejects.toThrow('invalid branch name');
+  expect(existsSync(worktreePath('a'))).toBe(true);
+});
+
+test('restoreWorktree re-creates a removed worktree from its kept branch', async () => {
+  const wt = await ensureWorktree(repo, 'restorable');
+  await gitIn(wt.path, 'commit', '-q', '--allow-empty', '-m', 'keep me');
+  await removeWorktree(repo, wt.path);
+
+  const restored = await restoreWorktree(repo, 'restorable');
+
+  expect(restored.path).toBe(wt.path);
+  const { stdout } = await gitIn(restored.path, 'log', '-1', '--format=%s');
+  expect(stdout.trim()).toBe('keep me');
+  await expect(restoreWorktree(repo, 'restorable')).rejects.toThrow('already checked out');
+});
+
+test('restoreWorktree refuses a branch that does not exist', async () => {
+  await expect(restoreWorktree(repo, 'never-made')).rejects.toThrow(
+    'no branch to restore: kild/never-made',
+  );
+  expect(existsSync(worktreePath('never-made'))).toBe(false);
+});
+
+test('forkWorktree branches from the source tip into a distinct tree', async () => {
+  const source = await ensureWorktree(repo, 'idea');
+  await gitIn(source.path, 'commit', '-q', '--allow-empty', '-m', 'promising');
+  const fork = await forkWorktree(repo, 'idea', 'idea-b');
+  expect(fork.branch).toBe('kild/idea-b');
+  expect(fork.path).not.toBe(source.path);
+  expect((await gitIn(fork.path, 'log', '-1', '--format=%s')).stdout.trim()).toBe('promising');
+  expect((await gitIn(source.path, 'status', '--porcelain')).stdout).toBe('');
+});
+
+test('forkWorktree refuses a missing source, a taken name, and an invalid name', async () => {
+  await ensureWorktree(repo, 'idea');
+  await ensureWorktree(repo, 'taken');
+  await expect(forkWorktree(repo, 'ghost', 'x')).rejects.toThrow('no such worktree');
+  await expect(forkWorktree(repo, 'idea', 'taken')).rejects.toThrow('already exists');
+  await expect(forkWorktree(repo, 'idea', '-bad')).rejects.toThrow('invalid branch name');
+});
+
+test('rebaseWorktree reports up to date, then a clean rebase with its commit count', async () => {
+  const wt = await ensureWorktree(repo, 'feat');
+  await gitIn(wt.path, 'commit', '-q', '--allow-empty', '-m', 'work');
+  expect(await rebaseWorktree(repo, 'feat', 'main')).toEqual({ ok: true, status: 'up_to_date' });
+
+  await git('commit', '-q', '--allow-empty', '-m', 'upstream');
+  expect(await rebaseWorktree(repo, 'feat', 'main')).toEqual({
+    ok: true,
+    status: 'rebased',
+    commits: 1,
+  });
+  expect((await gitIn(wt.path, 'log', '-1', '--format=%s', 'HEAD~1')).stdout.trim()).toBe(
+    'upstream',
+  );
+});
+
+test('rebaseWorktree leaves a conflict in progress, naming the files; abort restores', async () => {
+  const wt = await ensureWorktree(repo, 'clash');
+  writeFileSync(path.join(wt.path, 'f.txt'), 'ours\n');
+  await gitIn(wt.path, 'add', 'f.txt');
+  await gitIn(wt.path, 'commit', '-q', '-m', 'ours');
+  const before = (await gitIn(wt.path, 'rev-parse', 'HEAD')).stdout;
+  writeFileSync(path.join(repo, 'f.txt'), 'theirs\n');
+  await git('add', 'f.txt');
+  await git('commit', '-q', '-m', 'theirs');
+
+  expect(await rebaseWorktree(repo, 'clash', 'main')).toEqual({
+    ok: false,
+    code: 'conflict',
+    files: ['f.txt'],
+    path: wt.path,
+  });
+  // Stopped mid-rebase the tree is detached, but still listed on its branch.
+  expect(await findWorktree(repo, 'clash')).toMatchObject({ branch: 'kild/clash' });
+  await abortRebase(repo, 'clash');
+  expect((await gitIn(wt.path, 'rev-parse', 'HEAD')).stdout).toBe(before);
+  expect((await gitIn(wt.path, 'status', '--porcelain')).stdout).toBe('');
+});
+
+test('rebaseWorktree refuses a tree in use, tracked changes and a missing tree', async () => {
+  const wt = await ensureWorktree(repo, 'wip');
+  writeFileSync(path.join(wt.path, 'a.txt'), 'a\n');
+  await gitIn(wt.path, 'add', 'a.txt');
+  await git('commit', '-q', '--allow-empty', '-m', 'upstream');
+  expect(await rebaseWorktree(repo, 'wip', 'main')).toEqual({
+    ok: false,
+    code: 'dirty',
+    files: ['a.txt'],
+  });
+  expect(await rebaseWorktree(repo, 'nope', 'main')).toEqual({ ok: false, code: 'not_found' });
+  expect(await rebaseWorktree(repo, 'wip', 'main', true)).toEqual({ ok: false, code: 'in_use' });
+});
+
+test('findWorktree locates a checked-out worktree and misses a removed one', async () => {
+  const wt = await ensureWorktree(repo, 'feat/where');
+  expect(await findWorktree(repo, 'feat/where')).toMatchObject({
+    branch: 'kild/feat/where',
+    name: 'feat/where',
+  });
+  expect(await findWorktree(repo, 'nope')).toBeUndefined();
+
+  await removeWorktree(repo, wt.path);
+  expect(await findWorktree(repo, 'feat/where')).toBeUndefined();
+});
+
+test('a slashed tree at its legacy dash-joined dir is still found and attached', async () => {
+  const legacy = path.join(home, 'worktrees', 'feat-login');
+  await git('worktree', 'add', '-q', '-b', 'kild/feat/login', legacy);
+  const expected = { branch: 'kild/feat/login', name: 'feat/login' };
+
+  expect(await findWorktree(repo, 'feat/login')).toMatchObject(expected);
+  const attached = await ensureWorktree(repo, 'feat/login');
+  expect(attached).toMatchObject(expected);
+  expect(existsSync(worktreePath('feat/login'))).toBe(false);
+});
+
+test('a detached kild tree is found by its dir, and adopt refuses it', async () => {
+  const wt = await ensureWorktree(repo, 'feat/detached');
+  await gitIn(wt.path, 'checkout', '-q', '--detach');
+
+  expect(await findWorktree(repo, 'feat/detached')).toMatchObject({
+    branch: '(detached)',
+    name: 'feat/detached',
+  });
+  await expect(adoptWorktree(repo, wt.path)).rejects.toThrow(
+    'already a kild worktree: kild/feat/detached',
+  );
+});
+
+test('ensureWorktree starts a new branch from a tag or a SHA, and names a bad ref', async () => {
+  await git('commit', '-q', '--allow-empty', '-m', 'v1');
+  await git('tag', 'v1');
+  const sha = (await git('rev-parse', 'HEAD')).stdout.trim();
+  await git('commit', '-q', '--allow-empty', '-m', 'later');
+
+  const fromTag = await ensureWorktree(repo, 'from-tag', 'v1');
+  const fromSha = await ensureWorktree(repo, 'from-sha', sha.slice(0, 10));
+  expect((await gitIn(fromTag.path, 'rev-parse', 'HEAD')).stdout.trim()).toBe(sha);
+  expect((await gitIn(fromSha.path, 'rev-parse', 'HEAD')).stdout.trim()).toBe(sha);
+  await expect(ensureWorktree(repo, 'nowhere', 'no-such-ref')).rejects.toThrow(
+    'start ref not found: no-such-ref',
+  );
+  expect(existsSync(worktreePath('nowhere'))).toBe(false);
+});
+
+test('isGitRepo finds a checkout, a worktree and a bare repo — not a dir in one', async () => {
+  const wt = await ensureWorktree(repo, 'probe'); // `.git` is a file here
+  const sub = path.join(repo, 'nested', 'dir');
+  mkdirSync(sub, { recursive: true });
+  const bare = path.join(home, 'bare.git');
+  await execFile('git', ['init', '-q', '--bare', bare]);
+  const plain = path.join(home, 'plain');
+  mkdirSync(plain);
+
+  expect(await isGitRepo(repo)).toBe(true);
+  expect(await isGitRepo(wt.path)).toBe(true);
+  expect(await isGitRepo(sub)).toBe(false);
+  expect(await isGitRepo(bare)).toBe(true);
+  expect(await isGitRepo(plain)).toBe(false);
+});
@@ -1,6 +1,14 @@
 import { expect, test } from 'bun:test';
 
-import { assertSafeBranch, worktreePath, worktreeRef } from './worktree.ts';
+import {
+  assertSafeBranch,
+  decodeWorktreeDir,
+  encodeWorktreeDir,
+  legacyWorktreeDir,
+  worktreeNameForDir,
+  worktreePath,
+  worktreeRef,
+} from './worktree.ts';
 
 test('assertSafeBranch accepts ordinary + slashed names', () => {
   expect(() => assertSafeBranch('fix-auth')).not.toThrow();
@@ -15,16 +23,45 @@ test('assertSafeBranch rejects injection-shaped names', () => {
   expect(() => assertSafeBranch('a;b')).toThrow();
 });
 
+test('assertSafeBranch rejects names already carrying the kild/ prefix', () => {
+  expect(() => assertSafeBranch('kild/x')).toThrow('kild/ is added for you');
+  expect(() => worktreeRef('kild/feat/x')).toThrow('invalid branch name');
+  expect(() => assertSafeBranch('kild-x')).not.toThrow();
+  expect(() => assertSafeBranch('feat/kild/x')).not.toThrow();
+});
+
 test('worktreeRef prefixes kild/', () => {
   expect(worktreeRef('x')).toBe('kild/x');
   expect(worktreeRef('feat/x')).toBe('kild/feat/x');
 });
 
-test('worktreePath slashes become dashes under worktrees/', () => {
-  expect(worktreePath('a/b').replace(/\\/g, '/')).toEndWith('/worktrees/a-b');
+test('worktreePath escapes slashes under worktrees/', () => {
+  expect(worktreePath('a/b').replace(/\\/g, '/')).toEndWith('/worktrees/a%2Fb');
   expect(worktreePath('fix-auth').replace(/\\/g, '/')).toEndWith('/worktrees/fix-auth');
 });
 
+test('the dir encoding round-trips and keeps slash, dash and underscore names apart', () => {
+  const names = ['feat/login', 'feat-login', 'feat_x/auth', 'feat/x/auth', 'a/b/c_d-e'];
+  const dirs = names.map(encodeWorktreeDir);
+
+  expect(new Set(dirs).size).toBe(names.length);
+  expect(dirs.map(decodeWorktreeDir)).toEqual(names);
+  expect(dirs.every((dir) => !dir.includes('/'))).toBe(true);
+});
+
+test('a dir under the root names its worktree by decoding, or by the legacy dashes', () => {
+  const root = '/home/u/.kild/worktrees';
+  const names = ['feat/login', 'fix-auth', 'a/b'];
+
+  expect(legacyWorktreeDir('feat/x/auth')).toBe('feat-x-auth');
+  expect(worktreeNameForDir(`${root}/feat%2Flogin`, root, names)).toBe('feat/login');
+  expect(worktreeNameForDir(`${root}/a-b`, root, names)).toBe('a/b');
+  expect(worktreeNameForDir(`${root}/fix-auth`, root, names)).toBe('fix-auth');
+  expect(worktreeNameForDir(`${root}/other`, root, names)).toBeUndefined();
+  expect(worktreeNameForDir(`${root}/fix-auth/sub`, root, names)).toBeUndefined();
+  expect(worktreeNameForDir('/elsewhere/fix-auth', root, names)).toBeUndefined();
+});
+
 test('the derivation helpers reject before any I/O', () => {
   expect(() => worktreeRef('--x')).toThrow();
   expect(() => worktreePath('$(x)')).toThrow();
@@ -1,9 +1,23 @@
 import { execFile as execFileCb } from 'node:child_process';
-import { existsSync, realpathSync } from 'node:fs';
+import {
+  accessSync,
+  constants,
+  existsSync,
+  mkdirSync,
+  readFileSync,
+  realpathSync,
+  statSync,
+} from 'node:fs';
 import path from 'node:path';
 import { promisify } from 'node:util';
 
-import { configuredBaseBranch, kildHome } from './config.ts';
+import {
+  configuredBaseBranch,
+  configuredDirtyPolicy,
+  configuredWorktreeRoot,
+  kildHome,
+} from './config.ts';
+import { matchesAnyGlob } from './glob.ts';
 
 // execFile (no shell) + a branch-name allowlist: the brain's create_worktree tool
 // and the cockpit's worktree selector feed a (possibly LLM-generated) name in here,
@@ -24,14 +38,21 @@ export interface Worktree {
   name?: string;
 }
 
-export function worktreesRoot(): string {
-  return path.join(kildHome(), 'worktrees');
+/** Where worktrees live: the configured `worktreeRoot` (`repo`'s project value over the
+ *  global one), else `$KILD_HOME/worktrees`. */
+export function worktreesRoot(repo?: string): string {
+  return configuredWorktreeRoot(repo) ?? path.join(kildHome(), 'worktrees');
 }
 
 export function assertSafeBranch(branch: string): void {
   if (branch.startsWith('-') || !/^[A-Za-z0-9._/-]+$/.test(branch)) {
     throw new Error(`invalid branch name: ${branch}`);
   }
+  // Names get the `kild/` prefix to make their branch. A name already carrying it is a
+  // branch passed where a name belongs, and would silently create `kild/kild/x`.
+  if (branch.startsWith('kild/')) {
+    throw new Error(`invalid branch name: ${branch} (kild/ is added for you — drop it)`);
+  }
 }
 
 /** The `kild/<name>` branch ref a worktree name maps to. Deterministic, no I/O. */
@@ -46,11 +67,82 @@ export function worktreeName(branch: string): string {
   return branch.replace(/^kild\//, '');
 }
 
-/** The on-disk path a worktree name maps to. Deterministic, no I/O — so the engine
- *  can fill `SessionInfo.worktreePath` synchronously before the worker creates it. */
-export function worktreePath(name: string): string {
+/** The directory name a worktree name is stored under: each `/` becomes `%2F`. `%`
+ *  can't occur in a name ({@link assertSafeBranch}), so this is reversible — unlike a
+ *  `-`, which made `feat/login` and `feat-login` share one dir. */
+export function encodeWorktreeDir(name: string): string {
+  return name.replace(/\//g, '%2F');
+}
+
+/** The worktree name a directory under {@link worktreesRoot} holds (the inverse of
+ *  {@link encodeWorktreeDir}). */
+export function decodeWorktreeDir(dir: string): string {
+  return dir.replace(/%2F/g, '/');
+}
+
+/** The dir name trees were stored under before {@link encodeWorktreeDir}: each `/`
+ *  became `-`. Lossy (`feat/login` and `feat-login` share it), so it is only ever
+ *  matched against a known name, never decoded. */
+export function legacyWorktreeDir(name: string): string {
+  return name.replace(/\//g, '-');
+}
+
+/** Which of `names` is stored at `treePath`, a dir directly under `root`: the name the
+ *  dir decodes to, else one whose legacy dash-joined dir it is. Undefined for a path
+ *  elsewhere. For a tree with no `kild/*` branch checked out to name it (a detached
+ *  HEAD), where the dir is all that says whose it is. */
+export function worktreeNameForDir(
+  treePath: string,
+  root: string,
+  names: Iterable<string>,
+): string | undefined {
+  const dir = path.relative(root, treePath);
+  if (!dir || dir.startsWith('..') || path.isAbsolute(dir) || dir.includes(path.sep)) {
+    return undefined;
+  }
+  const candidates = [...names];
+  const decoded = decodeWorktreeDir(dir);
+  if (candidates.includes(decoded)) return decoded;
+  return candidates.find((name) => legacyWorktreeDir(name) === dir);
+}
+
+/** {@link worktreesRoot} as git reports paths — through realpath (macOS presents /var as
+ *  /private/var). Unresolved when it doesn't exist yet. */
+export function resolvedWorktreesRoot(repo?: string): string {
+  const root = worktreesRoot(repo);
+  return existsSync(root) ? realpathSync(root) : root;
+}
+
+/** The on-disk path a worktree name of `repo` maps to — where a NEW tree is created.
+ *  Deterministic and synchronous — so the engine can fill `SessionInfo.worktreePath`
+ *  before the worker creates it. An existing tree may live elsewhere (made under an
+ *  earlier `worktreeRoot`, or at a legacy dir): find it with {@link findWorktree}. */
+export function worktreePath(name: string, repo?: string): string {
   assertSafeBranch(name);
-  return path.join(worktreesRoot(), name.replace(/\//g, '-'));
+  return path.join(worktreesRoot(repo), encodeWorktreeDir(name));
+}
+
+/** Is `dir` the top of a git repository — a checkout, a linked worktree or submodule
+ *  (where `.git` is a file), or a bare repo? Cheap first: stat `dir/.git`. Only when
+ *  that's absent is git asked, and then `dir` itself must be the repo's top: a plain dir
+ *  nested inside some checkout (a project whose repo moved away) is not a repo. */
+export async function isGitRepo(dir: string): Promise<boolean> {
+  if (existsSync(path.join(dir, '.git'))) return true;
+  const revParse = (...args: string[]) =>
+    execFile('git', ['-C', dir, 'rev-parse', ...args])
+      .then((r) => r.stdout.trim())
+      .catch(() => undefined);
+  const top =
+    (await revParse('--show-toplevel')) ??
+    ((await revParse('--is-bare-repository')) === 'true'
+      ? await revParse('--absolute-git-dir')
+      : undefined);
+  if (!top) return false;
+  try {
+    return realpathSync(top) === realpathSync(dir);
+  } catch {
+    return false;
+  }
 }
 
 /** The checkout's current branch (e.g. `dev`), or undefined if detached/unavailable. */
@@ -79,7 +171,7 @@ export async function createWorktree(
   base?: string,
 ): Promise<Worktree> {
   assertSafeBranch(branch);
-  const wtPath = worktreePath(branch);
+  const wtPath = worktreePath(branch, repo);
   const ref = worktreeRef(branch);
   // Best-effort pre-clean of a same-named worktree before the force re-create.
   // Force is intentional here ("new worktree" is destructive-by-request).
@@ -95,54 +187,140 @@ export async function createWorktree(
  *  joins). Never resets: an existing dir attaches; an existing *branch* (worktree
  *  removed but branch kept) is checked out, preserving its commits. */
 export async function ensureWorktree(repo: string, name: string, base?: string): Promise<Worktree> {
-  const wtPath = worktreePath(name);
+  const wtPath = worktreePath(name, repo);
   const ref = worktreeRef(name);
   const attached = { branch: ref, path: wtPath, name };
+  // A tree git already has for the branch is attached where it is — e.g. a slashed name
+  // made at its legacy dash-joined dir, before the current encoding.
+  const existing = await findWorktree(repo, name).catch(() => undefined);
+  if (existing) return existing;
   if (existsSync(wtPath)) {
     // Attach only to a real linked worktree (the `.git` pointer file). A leftover or
     // corrupt dir must NOT silently become a non-isolated cwd — fail fast instead.
     if (existsSync(path.join(wtPath, '.git'))) return attached;
     throw new Error(`worktree path exists but is not a git worktree: ${wtPath}`);
   }
+  assertWritableRoot(repo);
   try {
-    // The branch may already exist (the worktree was removed but the branch kept).
-    // Check it out — never `-B` (which would reset and lose its commits). A brand-new
-    // branch forks from `base` (e.g. `dev`) so it isn't accidentally based on `main`.
-    const branchExists = await execFile('git', ['-C', repo, 'rev-parse', '--verify', ref])
-      .then(() => true)
-      .catch(() => false);
-    if (branchExists) {
-      await execFile('git', ['-C', repo, 'worktree', 'add', wtPath, ref]);
-    } else {
-      await execFile('git', [
-        '-C',
-        repo,
-        'worktree',
-        'add',
-        '-b',
-        ref,
-        wtPath,
-        ...(base ? [base] : []),
-      ]);
-    }
+    await addWorktree(repo, wtPath, ref, base);
   } catch (err) {
     // Cold-start race: a concurrent session creating the *same* new worktree between
     // our existsSync check and `worktree add` wins, and ours fails ("already exists").
     // N agents sharing one fresh tree is valid, so attach to the real worktree it left
-    // behind; only re-throw if the path still isn't a git worktree.
+    // behind.
     if (existsSync(path.join(wtPath, '.git'))) return attached;
-    throw err;
+    // A tree deleted behind git's back (or a crash mid-create) leaves a registration
+    // whose dir is gone, and git refuses to add over it. Nothing is lost by pruning
+    // it — the dir is already gone — so clear it and retry once.
+    if (!(await hasStaleRegistration(repo, ref, wtPath))) throw err;
+    await execFile('git', ['-C', repo, 'worktree', 'prune']);
+    await addWorktree(repo, wtPath, ref, base);
   }
   return attached;
 }
 
+/** Fail fast, naming the root, when new worktrees can't be created under it — rather
+ *  than surfacing git's less direct error from `worktree add`. Only the default
+ *  `$KILD_HOME/worktrees` is created on demand: a configured `worktreeRoot` must already
+ *  be a writable dir, so one on an unmounted volume errors instead of quietly filling
+ *  the mount point on the wrong disk. */
+function assertWritableRoot(repo: string): void {
+  const configured = configuredWorktreeRoot(repo);
+  const root = configured ?? worktreesRoot(repo);
+  try {
+    if (!configured) mkdirSync(root, { recursive: true });
+    else if (!statSync(root).isDirectory()) throw new Error('not a directory');
+    accessSync(root, constants.W_OK);
+  } catch (err) {
+    throw new Error(`worktree root is not writable: ${root} (${errText(err)})`);
+  }
+}
+
+async function addWorktree(
+  repo: string,
+  wtPath: string,
+  ref: string,
+  base?: string,
+): Promise<void> {
+  // The branch may already exist (the worktree was removed but the branch kept).
+  // Check it out — never `-B` (which would reset and lose its commits). A brand-new
+  // branch forks from `base` (e.g. `dev`) so it isn't accidentally based on `main`.
+  const branchExists = await execFile('git', ['-C', repo, 'rev-parse', '--verify', ref])
+    .then(() => true)
+    .catch(() => false);
+  if (branchExists) {
+    await execFile('git', ['-C', repo, 'worktree', 'add', wtPath, ref]);
+  } else {
+    if (base) await assertStartRef(repo, base);
+    await execFile('git', [
+      '-C',
+      repo,
+      'worktree',
+      'add',
+      '-b',
+      ref,
+      wtPath,
+      ...(base ? [base] : []),
+    ]);
+  }
+}
+
+/** Fail fast, naming it, when a new branch's start point isn't a commit git can find —
+ *  any branch, tag, or SHA will do — rather than surfacing `worktree add`'s error. */
+async function assertStartRef(repo: string, start: string): Promise<void> {
+  const found = await execFile('git', [
+    '-C',
+    repo,
+    'rev-parse',
+    '--verify',
+    '--quiet',
+    '--end-of-options',
+    `${start}^{commit}`,
+  ])
+    .then(() => true)
+    .catch(() => false);
+  if (!found) throw new Error(`start ref not found: ${start}`);
+}
+
+/** Does git still register a worktree for `ref` (or at `wtPath`) whose dir is gone? */
+async function hasStaleRegistration(repo: string, ref: string, wtPath: string): Promise<boolean> {
+  const trees = await listWorktrees(repo).catch(() => []);
+  return trees.some(
+    (tree) => (tree.branch === ref || tree.path === wtPath) && !existsSync(tree.path),
+  );
+}
+
+/** The branch a detached tree at `treePath` is in the middle of rebasing, from the
+ *  rebase state git keeps in the tree's own git dir. Undefined when no rebase is in
+ *  progress (or the git dir can't be read). */
+function rebasingBranch(treePath: string): string | undefined {
+  try {
+    // A linked tree's `.git` is a `gitdir: <path>` file; the main checkout's is the dir.
+    const dotGit = path.join(treePath, '.git');
+    const gitDir = statSync(dotGit).isDirectory()
+      ? dotGit
+      : path.resolve(treePath, readFileSync(dotGit, 'utf8').replace(/^gitdir:/, '').trim());
+    for (const state of ['rebase-merge', 'rebase-apply']) {
+      const headName = path.join(gitDir, state, 'head-name');
+      if (existsSync(headName)) {
+        return readFileSync(headName, 'utf8').trim().replace(/^refs\/heads\//, '');
+      }
+    }
+  } catch {
+    // unreadable — report the tree as plainly detached
+  }
+  return undefined;
+}
+
+/** Every worktree of `repo`, as `git worktree list` has them. A tree stopped mid-rebase
+ *  is detached, but reported on the branch being rebased — it's still that branch's tree. */
 export async function listWorktrees(repo: string): Promise<Worktree[]> {
   const { stdout } = await execFile('git', ['-C', repo, 'worktree', 'list', '--porcelain']);
   const trees: Worktree[] = [];
   let cur: Partial<Worktree> = {};
   const push = () => {
     if (!cur.path) return;
-    const branch = cur.branch ?? '(detached)';
+    const branch = cur.branch ?? rebasingBranch(cur.path) ?? '(detached)';
     trees.push({
       path: cur.path,
       branch,
@@ -161,6 +339,46 @@ export async function listWorktrees(repo: string): Promise<Worktree[]> {
   return trees;
 }
 
+/** Worktree `name` as git has it checked out, or undefined if there is none (or its
+ *  dir is gone). Read from git rather than derived with {@link worktreePath}: a tree
+ *  made before `worktreeRoot` changed, or under its legacy dir, is still at its old
+ *  path. A detached tree is matched by its dir ({@link worktreeNameForDir}). */
+export async function findWorktree(repo: string, name: string): Promise<Worktree | undefined> {
+  const ref = worktreeRef(name);
+  const trees = (await listWorktrees(repo)).filter((tree) => existsSync(tree.path));
+  const checkedOut = trees.find((tree) => tree.branch === ref);
+  if (checkedOut) return checkedOut;
+  const root = resolvedWorktreesRoot(repo);
+  const detached = trees.find(
+    (tree) => tree.branch === '(detached)' && worktreeNameForDir(tree.path, root, [name]),
+  );
+  return detached && { ...detached, name };
+}
+
+/** Where worktree `name` of `repo` is: git's path for it ({@link findWorktree}), else
+ *  where {@link worktreePath} would create it. Never throws on a git failure — for
+ *  status probes, which report a missing tree themselves. */
+export async function locateWorktree(repo: string, name: string): Promise<string> {
+  const tree = await findWorktree(repo, name).catch(() => undefined);
+  return tree?.path ?? worktreePath(name, repo);
+}
+
+/** The names of `repo`'s `kild/*` branches, whether or not a tree has them checked out. */
+export async function kildBranchNames(repo: string): Promise<string[]> {
+  const { stdout } = await execFile('git', [
+    '-C',
+    repo,
+    'for-each-ref',
+    '--format=%(refname:short)',
+    'refs/heads/kild/',
+  ]);
+  return stdout
+    .split('\n')
+    .map((line) => line.trim())
+    .filter(Boolean)
+    .map(worktreeName);
+}
+
 /** A refusal to remove a worktree without an explicit destructive request. */
 export type WorktreeRemoveRefusal = {
   ok: false;
@@ -170,14 +388,15 @@ export type WorktreeRemoveRefusal = {
 
 export type WorktreeRemoveResult = { ok: true } | WorktreeRemoveRefusal;
 
-/** Files whose uncommitted changes would be discarded by removing `wtPath`. */
-async function changedFiles(wtPath: string): Promise<string[]> {
+/** Files whose uncommitted changes would be discarded by removing `wtPath`. Untracked
+ *  files count unless `includeUntracked` is false (config `dirtyIgnoresUntracked`). */
+async function changedFiles(wtPath: string, includeUntracked = true): Promise<string[]> {
   const { stdout } = await execFile('git', [
     '-C',
     wtPath,
     'status',
     '--porcelain',
-    '--untracked-files=all',
+    `--untracked-files=${includeUntracked ? 'all' : 'no'}`,
     '-z',
   ]);
   const files: string[] = [];
@@ -200,7 +419,9 @@ async function registeredWorktree(repo: string, wtPath: string): Promise<boolean
 
 /** Remove a worktree only when it is clean. Refusals are data so callers can give a
  * useful preview instead of parsing git's prose. `inUse` is supplied by the engine,
- * which alone knows about live sessions. */
+ * which alone knows about live sessions. The repo's configured dirty policy applies:
+ * untracked files (`dirtyIgnoresUntracked`) and `cleanIgnoreGlobs` paths don't make
+ * the tree dirty — and are removed with it. */
 export async function removeWorktree(
   repo: string,
   wtPath: string,
@@ -208,9 +429,21 @@ export async function removeWorktree(
 ): Promise<WorktreeRemoveResult> {
   if (inUse) return { ok: false, code: 'in_use' };
   if (!(await registeredWorktree(repo, wtPath))) return { ok: false, code: 'not_found' };
-  const files = await changedFiles(wtPath);
+  const policy = await configuredDirtyPolicy(repo);
+  const changed = await changedFiles(wtPath, !policy.ignoreUntracked);
+  const files = changed.filter((file) => !matchesAnyGlob(file, policy.ignoreGlobs));
   if (files.length > 0) return { ok: false, code: 'dirty', files };
-  await execFile('git', ['-C', repo, 'worktree', 'remove', wtPath]);
+  // git itself refuses any change without --force; the check above already established
+  // that every remaining change is one the policy says to discard.
+  const force = policy.ignoreUntracked || changed.length > 0;
+  await execFile('git', [
+    '-C',
+    repo,
+    'worktree',
+    'remove',
+    ...(force ? ['--force'] : []),
+    wtPath,
+  ]);
   return { ok: true };
 }
 
@@ -225,6 +458,170 @@ export async function forceRemoveWorktree(
   return { ok: true };
 }
 
+async function branchExists(repo: string, ref: string): Promise<boolean> {
+  return execFile('git', ['-C', repo, 'rev-parse', '--verify', '--quiet', `refs/heads/${ref}`])
+    .then(() => true)
+    .catch(() => false);
+}
+
+/** Re-create the worktree for a `kild/<name>` branch whose tree was removed (an orphan
+ *  branch), at its usual path, with the branch's commits intact. Unlike
+ *  {@link ensureWorktree} it never creates a branch: a missing one, or one another
+ *  worktree already has checked out, is an error. */
+export async function restoreWorktree(repo: string, name: string): Promise<Worktree> {
+  const ref = worktreeRef(name);
+  if (!(await branchExists(repo, ref))) throw new Error(`no branch to restore: ${ref}`);
+  const holder = (await listWorktrees(repo)).find((tree) => tree.branch === ref);
+  if (holder) throw new Error(`${ref} is already checked out at ${holder.path}`);
+  return ensureWorktree(repo, name);
+}
+
+/** Fork worktree `source` into a new worktree `name`, branched from `kild/<source>`'s
+ *  current tip rather than the base — to try a variation on a workstream without
+ *  disturbing it. Only committed work carries over (read from the branch, so the source
+ *  tree itself is never touched). Refuses a missing source and a `name` whose branch or
+ *  path is taken. */
+export async function forkWorktree(repo: string, source: string, name: string): Promise<Worktree> {
+  const sourceRef = worktreeRef(source);
+  const ref = worktreeRef(name);
+  if (!(await branchExists(repo, sourceRef))) throw new Error(`no such worktree: ${source}`);
+  if ((await branchExists(repo, ref)) || existsSync(worktreePath(name, repo))) {
+    throw new Error(`worktree '${name}' already exists`);
+  }
+  return ensureWorktree(repo, name, sourceRef);
+}
+
+/** A refusal to rename a worktree. `exists` means the new name's branch or path is taken. */
+export type WorktreeRenameResult =
+  | { ok: true; worktree: Worktree }
+  | { ok: false; code: 'in_use' | 'not_found' | 'exists' };
+
+/** Rename worktree `from` to `to`: the `kild/<from>` branch becomes `kild/<to>` and, if
+ *  its tree is checked out (wherever it is), the tree moves to `worktreePath(to)` —
+ *  commits and uncommitted work are kept, unlike rm + re-create. Invalid names throw;
+ *  refusals are data. `inUse` is supplied by the engine: a live session's cwd must not
+ *  move. */
+export async function renameWorktree(
+  repo: string,
+  from: string,
+  to: string,
+  inUse = false,
+): Promise<WorktreeRenameResult> {
+  const fromRef = worktreeRef(from);
+  const toRef = worktreeRef(to);
+  if (inUse) return { ok: false, code: 'in_use' };
+  if (!(await branchExists(repo, fromRef))) return { ok: false, code: 'not_found' };
+  const toPath = worktreePath(to, repo);
+  if ((await branchExists(repo, toRef)) || existsSync(toPath)) return { ok: false, code: 'exists' };
+
+  const tree = await findWorktree(repo, from);
+  if (tree) assertWritableRoot(repo); // `worktree move` won't create a new root's dir
+  await execFile('git', ['-C', repo, 'branch', '-m', fromRef, toRef]);
+  if (tree) {
+    try {
+      await execFile('git', ['-C', repo, 'worktree', 'move', tree.path, toPath]);
+    } catch (err) {
+      // Don't leave `kild/<to>` checked out at `<from>`'s path (e.g. a locked tree).
+      await execFile('git', ['-C', repo, 'branch', '-m', toRef, fromRef]).catch(() => {});
+      throw err;
+    }
+  }
+  return { ok: true, worktree: { branch: toRef, path: toPath, name: to } };
+}
+
+/** The outcome of rebasing a worktree. `conflict` leaves the rebase in progress for a
+ *  manual resolve (or {@link abortRebase}); `dirty` means uncommitted tracked changes
+ *  stopped it before it began. */
+export type WorktreeRebaseResult =
+  | { ok: true; status: 'up_to_date' }
+  | { ok: true; status: 'rebased'; commits: number }
+  | { ok: false; code: 'conflict'; files: string[]; path: string }
+  | { ok: false; code: 'dirty'; files: string[] }
+  | { ok: false; code: 'in_use' | 'not_found' };
+
+/** Rebase worktree `name`'s branch onto `base`, in its tree. A branch that already
+ *  contains `base` is left alone. `inUse` (the engine knows a live session is working
+ *  in the tree) refuses it — rewriting history under a running agent. Refusals and
+ *  conflicts are data; any other git failure throws. */
+export async function rebaseWorktree(
+  repo: string,
+  name: string,
+  base: string,
+  inUse = false,
+): Promise<WorktreeRebaseResult> {
+  if (inUse) return { ok: false, code: 'in_use' };
+  const tree = await findWorktree(repo, name);
+  if (!tree) return { ok: false, code: 'not_found' };
+  const wtPath = tree.path;
+  const dirty = await changedFiles(wtPath, false);
+  if (dirty.length > 0) return { ok: false, code: 'dirty', files: dirty };
+  const contains = ['-C', wtPath, 'merge-base', '--is-ancestor', base, 'HEAD'];
+  const upToDate = await execFile('git', contains)
+    .then(() => true)
+    .catch(() => false);
+  if (upToDate) return { ok: true, status: 'up_to_date' };
+  try {
+    await execFile('git', ['-C', wtPath, 'rebase', base]);
+  } catch (err) {
+    const { stdout } = await execFile('git', [
+      '-C',
+      wtPath,
+      'diff',
+      '--name-only',
+      '--diff-filter=U',
+    ]);
+    const files = stdout.split('\n').filter(Boolean);
+    if (files.length === 0) throw err;
+    return { ok: false, code: 'conflict', files, path: wtPath };
+  }
+  const { stdout } = await execFile('git', ['-C', wtPath, 'rev-list', '--count', `${base}..HEAD`]);
+  return { ok: true, status: 'rebased', commits: Number.parseInt(stdout, 10) };
+}
+
+/** Abort worktree `name`'s in-progress rebase, restoring the branch as it was. Throws
+ *  if there is no such tree or no rebase is in progress. */
+export async function abortRebase(repo: string, name: string): Promise<void> {
+  const tree = await findWorktree(repo, name);
+  if (!tree) throw new Error(`no such worktree: ${name}`);
+  await execFile('git', ['-C', tree.path, 'rebase', '--abort']);
+}
+
+/** Bring a hand-made worktree under kild: its branch `<b>` is renamed to `kild/<b>` and
+ *  the tree moved to `worktreePath(<b>)`, after which every kild verb sees it under the
+ *  name `<b>`. Commits and uncommitted changes move with it. Refuses the main checkout,
+ *  a detached tree, and a name whose `kild/` branch or path is already taken. */
+export async function adoptWorktree(repo: string, wtPath: string): Promise<Worktree> {
+  if (!existsSync(wtPath)) throw new Error(`no such directory: ${wtPath}`);
+  const target = realpathSync(wtPath);
+  const trees = await listWorktrees(repo);
+  const index = trees.findIndex((t) => existsSync(t.path) && realpathSync(t.path) === target);
+  const tree = trees[index];
+  if (!tree) throw new Error(`not a worktree of ${repo}: ${wtPath}`);
+  // `git worktree list` always leads with the main checkout, which can't be moved.
+  if (index === 0) throw new Error(`cannot adopt the main checkout: ${wtPath}`);
+  if (tree.name) throw new Error(`already a kild worktree: ${tree.branch}`);
+  if (tree.branch === '(detached)') {
+    const root = resolvedWorktreesRoot(repo);
+    const kildName = worktreeNameForDir(tree.path, root, await kildBranchNames(repo));
+    if (kildName) throw new Error(`already a kild worktree: kild/${kildName} (detached HEAD)`);
+    throw new Error(`worktree has a detached HEAD: ${wtPath}`);
+  }
+
+  const name = tree.branch;
+  const ref = worktreeRef(name);
+  const dest = worktreePath(name, repo);
+  if (await branchExists(repo, ref)) throw new Error(`branch already exists: ${ref}`);
+  const inPlace = existsSync(dest) && realpathSync(dest) === target;
+  if (existsSync(dest) && !inPlace) throw new Error(`worktree path already taken: ${dest}`);
+
+  if (!inPlace) assertWritableRoot(repo); // `worktree move` won't create the root's dir
+  await execFile('git', ['-C', repo, 'branch', '-m', name, ref]);
+  if (!inPlace) {
+    await execFile('git', ['-C', repo, 'worktree', 'move', tree.path, dest]);
+  }
+  return { branch: ref, path: dest, name };
+}
+
 /** The repo's default branch: `origin/HEAD` if set, else `main`/`master` if they
  *  exist, else the current branch. Used to decide which `kild/*` branches are merged. */
 async function defaultBranch(repo: string): Promise<string> {
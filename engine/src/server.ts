@@ -4,11 +4,9 @@ if (process.env.KILD_ROLE === 'worker') {
   await (await import('./worker.ts')).runWorker();
 }
 
-import { execFile as execFileCb } from 'node:child_process';
 import { randomUUID } from 'node:crypto';
 import fs from 'node:fs/promises';
 import path from 'node:path';
-import { promisify } from 'node:util';
 
 import { type Context, Hono } from 'hono';
 import { createBunWebSocket } from 'hono/bun';
@@ -16,7 +14,9 @@ import { cors } from 'hono/cors';
 
 import { listAgents } from './kild/agents.ts';
 import { reviewCommits, reviewDiff, reviewFiles } from './kild/git-review.ts';
+import { openExternal } from './kild/opener.ts';
 import { addProject, findProject, loadProjects } from './kild/projects.ts';
+import { pruneMergedPrWorktrees } from './kild/pr-prune.ts';
 import {
   resolveCloseRoomActor,
   resolveOpenRoomActor,
@@ -27,16 +27,18 @@ import type { CommandResult, ParticipantSpec } from './kild/room/room-types.ts';
 import { readSessionTranscript } from './kild/session-transcript.ts';
 import { sessionManager } from './kild/sessions.ts';
 import {
-  assertSafeBranch,
+  abortRebase,
+  findWorktree,
   forceRemoveWorktree,
+  isGitRepo,
   listWorktrees,
   pruneMergedWorktrees,
+  rebaseWorktree,
   removeWorktree,
-  worktreePath,
+  renameWorktree,
   worktreesRoot,
 } from './kild/worktree.ts';
 
-const execFile = promisify(execFileCb);
 const errText = (err: unknown): string => (err instanceof Error ? err.message : String(err));
 
 const PORT = Number(process.env.KILD_PORT ?? 4517);
@@ -70,7 +72,7 @@ app.get('/api/health', (c) => c.json({ ok: true, name: 'kild-engine', bootId: BO
 // ── Projects ────────────────────────────────────────────────────────────────
 app.get('/api/projects', async (c) => c.json(await loadProjects()));
 app.post('/api/projects', async (c) => {
-  const { name, path } = await c.req.json<{ name: string; path: string }>();
+  const { name, path } = await c.req.json<{ name?: string; path: string }>();
   try {
     return c.json(await addProject(name, path));
   } catch (err) {
@@ -154,13 +156,16 @@ app.delete('/api/worktrees', async (c) => {
     return c.json({ error: 'force must be a boolean' }, 400);
   }
   try {
-    assertSafeBranch(name); // allowlist before building a path under worktreesRoot()
-    const wtPath = worktreePath(name);
+    // Removed where git has it — a tree made under an earlier worktreeRoot is still
+    // at its old path. findWorktree validates the name first.
+    const tree = await findWorktree(repo, name);
     const result = worktreesInUse().has(name)
       ? { ok: false as const, code: 'in_use' as const }
-      : force
-        ? await forceRemoveWorktree(repo, wtPath)
-        : await removeWorktree(repo, wtPath);
+      : !tree
+        ? { ok: false as const, code: 'not_found' as const }
+        : force
+          ? await forceRemoveWorktree(repo, tree.path)
+          : await removeWorktree(repo, tree.path);
     if (!result.ok) {
       const error =
         result.code === 'dirty'
@@ -179,11 +184,66 @@ app.delete('/api/worktrees', async (c) => {
   }
 });
 
+app.post('/api/worktrees/rename', async (c) => {
+  const { project, from, to } = await c.req.json<{ project: string; from: string; to: string }>();
+  const repo = await resolveProjectPath(project);
+  if (!repo) return c.json({ error: 'project required' }, 400);
+  try {
+    const result = await renameWorktree(repo, from, to, worktreesInUse().has(from));
+    if (!result.ok) {
+      const error =
+        result.code === 'in_use'
+          ? `worktree '${from}' is in use by a live session`
+          : result.code === 'exists'
+            ? `worktree '${to}' already exists`
+            : `worktree '${from}' was not found`;
+      return c.json({ error, code: result.code }, result.code === 'not_found' ? 404 : 409);
+    }
+    return c.json(result.worktree);
+  } catch (err) {
+    return c.json({ error: String(err instanceof Error ? err.message : err) }, 400);
+  }
+});
+
+// A rebase (or its abort) rewrites the tree's history and files, so it is refused while
+// a live session works there — which only the engine knows. Conflicts and a dirty tree
+// come back as data, like the CLI's own result; `base` is resolved by the caller.
+app.post('/api/worktrees/rebase', async (c) => {
+  const { project, name, base, abort } = await c.req.json<{
+    project: string;
+    name: string;
+    base?: string;
+    abort?: boolean;
+  }>();
+  const repo = await resolveProjectPath(project);
+  if (!repo) return c.json({ error: 'project required' }, 400);
+  if (!abort && !base) return c.json({ error: 'base required' }, 400);
+  if (worktreesInUse().has(name)) {
+    return c.json({ error: `worktree '${name}' is in use by a live session`, code: 'in_use' }, 409);
+  }
+  try {
+    if (abort) {
+      await abortRebase(repo, name);
+      return c.json({ ok: true, name });
+    }
+    return c.json(await rebaseWorktree(repo, name, base as string));
+  } catch (err) {
+    return c.json({ error: String(err instanceof Error ? err.message : err) }, 400);
+  }
+});
+
 app.post('/api/worktrees/prune', async (c) => {
-  const { project } = await c.req.json<{ project: string }>();
+  const { project, prs, dryRun, names } = await c.req.json<{
+    project: string;
+    prs?: boolean;
+    dryRun?: boolean;
+    names?: string[];
+  }>();
   const repo = await resolveProjectPath(project);
   if (!repo) return c.json({ error: 'project required' }, 400);
   try {
+    // `prs`: prune by the forge's merged-PR state (catches squash merges) instead.
+    if (prs) return c.json(await pruneMergedPrWorktrees(repo, worktreesInUse(), dryRun, names));
     const pruned = await pruneMergedWorktrees(repo, worktreesInUse());
     return c.json({ pruned });
   } catch (err) {
@@ -192,19 +252,19 @@ app.post('/api/worktrees/prune', async (c) => {
 });
 
 // ── Open in OS ────────────────────────────────────────────────────────────────
-// Reveal a worktree path in the OS file browser. Only paths under the worktree root
-// are allowed — the engine is loopback-only but must never shell `open` on an
-// arbitrary path. Keeps the cockpit pure-web (no Tauri opener API needed).
+// Reveal a worktree path in the OS file browser. Only paths under a worktree root (the
+// global one, or a registered project's own) are allowed — the engine is loopback-only
+// but must never shell `open` on an arbitrary path. Keeps the cockpit pure-web (no Tauri
+// opener API needed).
 app.post('/api/open', async (c) => {
   const { path: target } = await c.req.json<{ path: string }>();
-  const root = worktreesRoot();
+  const roots = [worktreesRoot(), ...(await loadProjects()).map((p) => worktreesRoot(p.path))];
   const resolved = path.resolve(target ?? '');
-  if (resolved !== root && !resolved.startsWith(`${root}${path.sep}`)) {
+  if (!roots.some((root) => resolved === root || resolved.startsWith(`${root}${path.sep}`))) {
     return c.json({ error: 'path is not under the worktree root' }, 403);
   }
   try {
-    const opener = process.platform === 'darwin' ? 'open' : 'xdg-open';
-    await execFile(opener, [resolved]);
+    await openExternal(resolved, 'path');
     return c.json({ ok: true });
   } catch (err) {
     return c.json({ error: String(err instanceof Error ? err.message : err) }, 400);
@@ -213,7 +273,7 @@ app.post('/api/open', async (c) => {
 
 // Open an external http(s) URL in the OS browser. The cockpit routes rendered links
 // here so a click never navigates the Tauri webview away from the app. Restricted to
-// http/https — never file://, app schemes, etc. execFile (no shell) → no injection.
+// http/https — never file://, app schemes, etc. Opened via execFile (no shell).
 app.post('/api/open-url', async (c) => {
   const { url } = await c.req.json<{ url: string }>();
   let parsed: URL;
@@ -226,8 +286,7 @@ app.post('/api/open-url', async (c) => {
     return c.json({ error: 'only http(s) urls may be opened' }, 403);
   }
   try {
-    const opener = process.platform === 'darwin' ? 'open' : 'xdg-open';
-    await execFile(opener, [parsed.toString()]);
+    await openExternal(parsed.toString(), 'url');
     return c.json({ ok: true });
   } catch (err) {
     return c.json({ error: String(err instanceof Error ? err.message : err) }, 400);
@@ -283,6 +342,14 @@ app.get('/api/sessions/:id/transcript', async (c) => {
 // ── Sessions ──────────────────────────────────────────────────────────────────
 app.get('/api/sessions', (c) => c.json(sessionManager.list()));
 
+// The env a live session's worker was spawned with (what kild injected) — read-only.
+app.get('/api/sessions/:id/env', (c) => {
+  const id = c.req.param('id');
+  const env = sessionManager.env(id);
+  if (!env) return c.json({ error: `no such session: ${id}` }, 404);
+  return c.json(env);
+});
+
 // Spawn a detached session (e.g. a `kild fleet` driver) — the CLI/scripts drive this over
 // REST instead of holding a WS open. `fleet: true` grants the room-control tools.
 // `forkFrom` spawns the session from a frozen copy of an existing pi session file: the
@@ -471,14 +538,14 @@ app.post('/api/rooms/:id/close', async (c) => {
 // archived room's workstream dir is gone (409); unknown ids 404. Git failures inside
 // a resolved room are data ({error} in the body, per workstream-git-status), never a 500.
 app.get('/api/rooms/:id/git/commits', async (c) => {
-  const located = roomManager.workstreamDir(c.req.param('id'));
+  const located = await roomManager.workstreamDir(c.req.param('id'));
   if (!located.ok) {
     return c.json({ error: located.message, code: located.code }, roomResultStatus(located));
   }
   return c.json(await reviewCommits(located.value.dir, located.value.base));
 });
 app.get('/api/rooms/:id/git/files', async (c) => {
-  const located = roomManager.workstreamDir(c.req.param('id'));
+  const located = await roomManager.workstreamDir(c.req.param('id'));
   if (!located.ok) {
     return c.json({ error: located.message, code: located.code }, roomResultStatus(located));
   }
@@ -487,7 +554,7 @@ app.get('/api/rooms/:id/git/files', async (c) => {
 app.get('/api/rooms/:id/git/diff', async (c) => {
   const file = c.req.query('path');
   if (!file) return c.json({ error: 'path query parameter required' }, 400);
-  const located = roomManager.workstreamDir(c.req.param('id'));
+  const located = await roomManager.workstreamDir(c.req.param('id'));
   if (!located.ok) {
     return c.json({ error: located.message, code: located.code }, roomResultStatus(located));
   }
@@ -653,13 +720,14 @@ console.log(`kild-engine listening on http://${HOST}:${PORT}`);
 void loadProjects()
   .then((projects) =>
     Promise.all(
-      projects.map((p) =>
-        pruneMergedWorktrees(p.path, worktreesInUse()).catch((err) => {
-          // A non-git/unreadable project dir is expected (skip quietly-ish); anything
-          // else is logged rather than hidden.
+      projects.map(async (p) => {
+        // A non-git (or moved) project dir is expected — skip it without spawning git.
+        if (!(await isGitRepo(p.path))) return;
+        await pruneMergedWorktrees(p.path, worktreesInUse()).catch((err) => {
+          // Anything else is logged rather than hidden.
           console.warn(`kild: startup prune skipped ${p.name}: ${errText(err)}`);
-        }),
-      ),
+        });
+      }),
     ),
   )
   .catch((err) => console.warn(`kild: startup prune failed: ${errText(err)}`));
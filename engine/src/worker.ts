@@ -1,4 +1,5 @@
 import { randomUUID } from 'node:crypto';
+import { StringDecoder } from 'node:string_decoder';
 
 import {
   AuthStorage,
@@ -240,8 +241,9 @@ export async function runWorker(): Promise<never> {
   }
 
   let buf = '';
+  const decoder = new StringDecoder('utf8'); // keeps a split multibyte character intact
   process.stdin.on('data', (chunk: Buffer) => {
-    buf += chunk.toString();
+    buf += decoder.write(chunk);
     const lines = buf.split('\n');
     buf = lines.pop() ?? ''; // keep the incomplete trailing line
     for (const raw of lines) {